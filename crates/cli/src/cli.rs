@@ -1,10 +1,11 @@
 use crate::{player::Player, traits::ColorExt, Result};
-use reversi_core::{Board, Color, Game, Pos};
+use reversi_core::{Board, Color, Game, OpeningCatalog, Pos};
 
 pub struct Cli {
     game: Game,
     black_player: Box<dyn Player>,
     white_player: Box<dyn Player>,
+    openings: OpeningCatalog,
 }
 
 impl Cli {
@@ -13,6 +14,7 @@ impl Cli {
             game,
             black_player,
             white_player,
+            openings: OpeningCatalog::new(),
         }
     }
 
@@ -92,19 +94,31 @@ impl Cli {
         eprintln!();
     }
 
+    pub fn print_opening(&self) {
+        if let Some(opening) = self.openings.identify(&self.game) {
+            eprintln!("  Opening: {}", opening.name());
+            eprintln!();
+        }
+    }
+
     pub fn print_result(&self) {
         eprintln!();
 
-        let black = self.game.count_disk(Some(Color::Black));
-        let white = self.game.count_disk(Some(Color::White));
-        let winner = match black.cmp(&white) {
-            std::cmp::Ordering::Less => Some(self.player(Color::White)),
-            std::cmp::Ordering::Equal => None,
-            std::cmp::Ordering::Greater => Some(self.player(Color::Black)),
+        let result = match self.game.result() {
+            Some(result) => result,
+            None => return,
         };
-        match winner {
-            Some(player) => eprintln!("{} {} wins!", player.color().mark(), player.name()),
-            None => eprintln!("DRAW!"),
+        let black = result.score(Color::Black);
+        let white = result.score(Color::White);
+        match result.winner().map(|color| self.player(color)) {
+            Some(player) => eprintln!(
+                "{} {} wins! ({}-{})",
+                player.color().mark(),
+                player.name(),
+                black,
+                white
+            ),
+            None => eprintln!("DRAW! ({}-{})", black, white),
         }
 
         eprintln!();
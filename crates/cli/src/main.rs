@@ -40,6 +40,7 @@ fn main() -> Result<()> {
                 );
                 cli.print_board();
                 cli.print_score(Some(color));
+                cli.print_opening();
                 cli.do_turn(color)?;
             }
             None => {
@@ -47,6 +48,7 @@ fn main() -> Result<()> {
                 eprintln!("=== Turn #{}: Game Over ===", turn);
                 cli.print_board();
                 cli.print_score(None);
+                cli.print_opening();
                 cli.print_result();
                 break;
             }
@@ -111,14 +111,25 @@ impl Player for Computer {
             chosen,
             score,
             visited_nodes,
+            tt_hits,
+            tt_cutoffs,
+            pv,
         } = self.com.next_move(&self.evaluator, board);
         let elapsed = start.elapsed();
         let (best_pos, _) = chosen.ok_or("cannot find a pos to put")?;
 
         eprintln!("Computer's choice: {}", best_pos);
         eprintln!("Evaluation score: {}", score);
+        eprintln!(
+            "Expected line: {}",
+            pv.iter()
+                .map(|mv| mv.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
         eprintln!("  Thinking time: {:.2}", elapsed.as_secs_f64());
         eprintln!("  # of nodes: {}", visited_nodes);
+        eprintln!("  TT hits/cutoffs: {}/{}", tt_hits, tt_cutoffs);
         eprintln!(
             "  kNPS: {:.2}",
             visited_nodes as f64 / elapsed.as_secs_f64() / 1000.0
@@ -1,4 +1,4 @@
-use reversi_core::{Board, Pos, PosSet};
+use reversi_core::{Board, Pos, PosSet, Transform};
 use std::{
     collections::{BTreeMap, BTreeSet},
     fs::File,
@@ -227,34 +227,10 @@ struct PatternMap(BTreeMap<PosSet, BTreeSet<Vec<Pos>>>);
 impl PatternMap {
     fn from_pattern(pattern: Vec<Pos>) -> Self {
         let mut pattern_map = Self::default();
-        pattern_map.insert(pattern);
-
-        const MAX: i8 = Board::SIZE - 1;
-
-        fn insert_mapped(map: &mut PatternMap, f: impl Fn(Pos) -> Pos) {
-            for pattern in map.all_patterns() {
-                let flipped = pattern.into_iter().map(&f).collect();
-                map.insert(flipped);
-            }
+        for transform in Transform::ALL {
+            let mapped = pattern.iter().map(|p| p.transform(transform)).collect();
+            pattern_map.insert(mapped);
         }
-
-        // horizontal flipped
-        insert_mapped(&mut pattern_map, |p| {
-            Pos::from_xy(MAX - p.x(), p.y()).unwrap()
-        });
-
-        // vertical flipped
-        insert_mapped(&mut pattern_map, |p| {
-            Pos::from_xy(p.x(), MAX - p.y()).unwrap()
-        });
-
-        // rotate 90 deg 3times
-        for _ in 0..3 {
-            insert_mapped(&mut pattern_map, |p| {
-                Pos::from_xy(MAX - p.y(), p.x()).unwrap()
-            });
-        }
-
         pattern_map
     }
 
@@ -262,13 +238,6 @@ impl PatternMap {
         let set = pattern.iter().copied().collect::<PosSet>();
         self.0.entry(set).or_default().insert(pattern);
     }
-
-    fn all_patterns(&self) -> Vec<Vec<Pos>> {
-        self.0
-            .iter()
-            .flat_map(|(_, set)| set.iter().cloned())
-            .collect()
-    }
 }
 
 fn create_pattern_to_weight_map(
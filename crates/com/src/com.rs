@@ -1,11 +1,30 @@
+use self::tt::{Bound, Scores, TranspositionTable};
 use crate::Evaluate;
-use reversi_core::{Board, Pos};
+use reversi_core::{Board, Move, Pos, PosSet};
+use std::{
+    mem,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
 
-#[derive(Debug, Clone, Copy)]
+mod tt;
+
+#[derive(Debug, Clone)]
 pub struct NextMove {
     pub chosen: Option<(Pos, Board)>,
     pub visited_nodes: u32,
     pub score: i32,
+    // starts with the chosen move
+    pub pv: Vec<Move>,
+    pub tt_hits: u32,
+    pub tt_cutoffs: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchAlgorithm {
+    #[default]
+    AlphaBeta,
+    NegaScout,
 }
 
 #[derive(Debug)]
@@ -13,6 +32,11 @@ pub struct Com {
     mid_depth: u32,
     wld_depth: u32,
     exact_depth: u32,
+    tt_size: usize,
+    // kept across searches and allocated on the first search
+    tt: Mutex<TranspositionTable>,
+    move_ordering: bool,
+    algorithm: SearchAlgorithm,
 }
 
 impl Com {
@@ -21,60 +45,349 @@ impl Com {
             mid_depth,
             wld_depth,
             exact_depth,
+            tt_size: DEFAULT_TT_SIZE,
+            tt: Mutex::new(TranspositionTable::with_size(0)),
+            move_ordering: true,
+            algorithm: SearchAlgorithm::default(),
         }
     }
 
+    // in bytes; `0` disables the table
+    pub fn with_tt_size(mut self, bytes: usize) -> Self {
+        self.tt_size = bytes;
+        self
+    }
+
+    pub fn with_move_ordering(mut self, move_ordering: bool) -> Self {
+        self.move_ordering = move_ordering;
+        self
+    }
+
+    pub fn with_algorithm(mut self, algorithm: SearchAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    // entries of earlier searches are reused, as long as the same evaluator is used
+    fn table(&self, scores: Scores) -> MutexGuard<'_, TranspositionTable> {
+        // entries stored before a panic are still valid
+        let mut tt = self.tt.lock().unwrap_or_else(PoisonError::into_inner);
+        tt.resize(self.tt_size);
+        tt.new_search(scores);
+        tt
+    }
+
+    fn context<'a>(
+        &self,
+        tt: &'a mut TranspositionTable,
+        deadline: Option<Instant>,
+    ) -> SearchContext<'a> {
+        SearchContext::new(tt, self.move_ordering, self.algorithm, deadline)
+    }
+
     pub fn next_move(&self, evaluator: &impl Evaluate, board: &Board) -> NextMove {
         let left = board.count_disk(None);
-        if left <= self.exact_depth {
-            self.end_search(evaluator, board, left, (-i32::MAX, i32::MAX))
+        let scores = if left <= self.exact_depth || left <= self.wld_depth {
+            Scores::End
+        } else {
+            Scores::Mid
+        };
+        let mut tt = self.table(scores);
+        let mut ctx = self.context(&mut tt, None);
+        let (score, chosen) = if left <= self.exact_depth {
+            end_search(evaluator, board, left, (-i32::MAX, i32::MAX), &mut ctx)
         } else if left <= self.wld_depth {
-            self.end_search(evaluator, board, left, (-i32::MAX, 1))
+            end_search(evaluator, board, left, (-i32::MAX, 1), &mut ctx)
         } else {
-            self.mid_search(evaluator, board, self.mid_depth)
+            mid_search(evaluator, board, self.mid_depth, &mut ctx)
+        };
+        NextMove {
+            chosen,
+            visited_nodes: ctx.visited_nodes,
+            score,
+            pv: ctx.take_pv(),
+            tt_hits: ctx.tt_hits,
+            tt_cutoffs: ctx.tt_cutoffs,
         }
     }
 
-    fn end_search(
+    pub fn next_move_within(
         &self,
         evaluator: &impl Evaluate,
         board: &Board,
-        depth: u32,
-        (alpha, beta): (i32, i32),
+        budget: Duration,
     ) -> NextMove {
-        let alpha_beta = alpha_beta::<_, true>;
-        let mut visited_nodes = 0;
-        let (score, chosen) = alpha_beta(
-            evaluator,
-            board,
-            depth,
-            (alpha, beta),
-            false,
-            &mut visited_nodes,
-        );
+        let start = Instant::now();
+        let deadline = start + budget;
+        let left = board.count_disk(None);
+
+        // entries of shallower iterations are kept, as they are still valid bounds
+        let mut tt = self.table(Scores::Mid);
+        let mut stats = SearchStats::default();
+
+        // a one-ply search always completes so that there is a move to return
+        let mut ctx = self.context(&mut tt, None);
+        let (mut score, mut chosen) = mid_search(evaluator, board, 1, &mut ctx);
+        let mut pv = ctx.take_pv();
+        stats.add(&ctx);
+        let mut last_nodes = ctx.visited_nodes;
+        let mut branching = DEFAULT_BRANCHING;
+
+        let mut depth = 1;
+        while depth < left {
+            let elapsed = start.elapsed();
+            let remaining = budget.saturating_sub(elapsed).as_secs_f64();
+            let nodes_per_sec = stats.visited_nodes as f64 / elapsed.as_secs_f64().max(1e-6);
+            let estimate = |nodes: f64| nodes / nodes_per_sec;
+
+            // try to solve the endgame once the estimated solve fits in the remaining time
+            let solve_nodes = last_nodes as f64 * branching.powi((left - depth) as i32);
+            if estimate(solve_nodes) < remaining {
+                tt.new_search(Scores::End);
+                let mut ctx = self.context(&mut tt, Some(deadline));
+                let wld = end_search(evaluator, board, left, (-i32::MAX, 1), &mut ctx);
+                stats.add(&ctx);
+                if ctx.aborted {
+                    break;
+                }
+                (score, chosen) = wld;
+                pv = ctx.take_pv();
+
+                let mut ctx = self.context(&mut tt, Some(deadline));
+                let exact = end_search(evaluator, board, left, (-i32::MAX, i32::MAX), &mut ctx);
+                stats.add(&ctx);
+                if !ctx.aborted {
+                    (score, chosen) = exact;
+                    pv = ctx.take_pv();
+                }
+                break;
+            }
+
+            if estimate(last_nodes as f64 * branching) > remaining {
+                break;
+            }
+            depth += 1;
+            let mut ctx = self.context(&mut tt, Some(deadline));
+            let result = mid_search(evaluator, board, depth, &mut ctx);
+            stats.add(&ctx);
+            if ctx.aborted {
+                break;
+            }
+            (score, chosen) = result;
+            pv = ctx.take_pv();
+            branching = (ctx.visited_nodes as f64 / last_nodes.max(1) as f64)
+                .clamp(MIN_BRANCHING, MAX_BRANCHING);
+            last_nodes = ctx.visited_nodes;
+        }
+
         NextMove {
             chosen,
-            visited_nodes,
+            visited_nodes: stats.visited_nodes,
             score,
+            pv,
+            tt_hits: stats.tt_hits,
+            tt_cutoffs: stats.tt_cutoffs,
         }
     }
+}
 
-    fn mid_search(&self, evaluator: &impl Evaluate, board: &Board, depth: u32) -> NextMove {
-        let alpha_beta = alpha_beta::<_, false>;
+const DEFAULT_TT_SIZE: usize = 16 * 1024 * 1024;
 
-        let mut visited_nodes = 0;
-        let (score, chosen) = alpha_beta(
-            evaluator,
-            board,
-            depth,
-            (-i32::MAX, i32::MAX),
-            false,
-            &mut visited_nodes,
-        );
-        NextMove {
-            chosen,
-            visited_nodes,
-            score,
+const DEFAULT_BRANCHING: f64 = 8.0;
+const MIN_BRANCHING: f64 = 2.0;
+const MAX_BRANCHING: f64 = 16.0;
+
+// checking the clock on every node is too slow
+const ABORT_CHECK_INTERVAL: u32 = 1024;
+
+#[derive(Debug, Default)]
+struct SearchStats {
+    visited_nodes: u32,
+    tt_hits: u32,
+    tt_cutoffs: u32,
+}
+
+impl SearchStats {
+    fn add(&mut self, ctx: &SearchContext) {
+        self.visited_nodes += ctx.visited_nodes;
+        self.tt_hits += ctx.tt_hits;
+        self.tt_cutoffs += ctx.tt_cutoffs;
+    }
+}
+
+#[derive(Debug)]
+struct SearchContext<'a> {
+    visited_nodes: u32,
+    tt: &'a mut TranspositionTable,
+    tt_hits: u32,
+    tt_cutoffs: u32,
+    move_ordering: bool,
+    algorithm: SearchAlgorithm,
+    // `lines[ply]` is the principal variation of the node last searched at `ply`,
+    // kept between nodes so that collecting the lines does not allocate
+    lines: Vec<Vec<Move>>,
+    ply: usize,
+    deadline: Option<Instant>,
+    until_check: u32,
+    aborted: bool,
+}
+
+impl<'a> SearchContext<'a> {
+    fn new(
+        tt: &'a mut TranspositionTable,
+        move_ordering: bool,
+        algorithm: SearchAlgorithm,
+        deadline: Option<Instant>,
+    ) -> Self {
+        Self {
+            visited_nodes: 0,
+            tt,
+            tt_hits: 0,
+            tt_cutoffs: 0,
+            move_ordering,
+            algorithm,
+            lines: vec![],
+            ply: 0,
+            deadline,
+            until_check: ABORT_CHECK_INTERVAL,
+            aborted: false,
+        }
+    }
+
+    fn should_abort(&mut self) -> bool {
+        if let Some(deadline) = self.deadline {
+            self.until_check -= 1;
+            if self.until_check == 0 {
+                self.until_check = ABORT_CHECK_INTERVAL;
+                self.aborted = Instant::now() >= deadline;
+            }
+        }
+        self.aborted
+    }
+
+    fn line_mut(&mut self, ply: usize) -> &mut Vec<Move> {
+        if self.lines.len() <= ply {
+            self.lines.resize_with(ply + 1, Vec::new);
+        }
+        &mut self.lines[ply]
+    }
+
+    // the line at `ply` becomes `mv` followed by the line of the child searched last
+    fn update_line(&mut self, ply: usize, mv: Move) {
+        self.line_mut(ply + 1);
+        let (line, rest) = self.lines[ply..].split_first_mut().unwrap();
+        line.clear();
+        line.push(mv);
+        line.extend_from_slice(&rest[0]);
+    }
+
+    // the line below a cutoff follows the best moves stored in the table
+    fn table_line(&mut self, ply: usize, board: &Board, depth: u32) {
+        let mut line = mem::take(self.line_mut(ply));
+        line.clear();
+        let mut board = *board;
+        let mut depth = depth;
+        while depth > 0 {
+            if !board.can_play() {
+                if !board.reverse().can_play() {
+                    break;
+                }
+                line.push(Move::Pass);
+                board = board.reverse();
+            }
+            let next = self
+                .tt
+                .probe(board.hash64())
+                .and_then(|entry| entry.best())
+                .and_then(|pos| Some((pos, board.flipped(pos)?)));
+            let (pos, flipped) = match next {
+                Some(next) => next,
+                None => break,
+            };
+            line.push(Move::Put(pos));
+            board = flipped;
+            depth -= 1;
+        }
+        self.lines[ply] = line;
+    }
+
+    fn take_pv(&mut self) -> Vec<Move> {
+        mem::take(self.line_mut(0))
+    }
+
+    fn probe(&mut self, key: u64, board: &Board, depth: u32, (alpha, beta): (i32, i32)) -> Probe {
+        let entry = match self.tt.probe(key) {
+            Some(entry) => *entry,
+            None => return Probe::Miss(None),
+        };
+        self.tt_hits += 1;
+        if entry.depth() >= depth {
+            let cutoff = match entry.bound() {
+                Bound::Exact => true,
+                Bound::Lower => entry.score() >= beta,
+                Bound::Upper => entry.score() <= alpha,
+            };
+            if cutoff {
+                self.tt_cutoffs += 1;
+                let chosen = entry
+                    .best()
+                    .and_then(|pos| Some((pos, board.flipped(pos)?)));
+                self.table_line(self.ply, board, depth);
+                return Probe::Cutoff(entry.score(), chosen);
+            }
+        }
+        Probe::Miss(entry.best())
+    }
+
+    fn store(
+        &mut self,
+        key: u64,
+        depth: u32,
+        score: i32,
+        (alpha, beta): (i32, i32),
+        chosen: Option<(Pos, Board)>,
+    ) {
+        let bound = Bound::new(score, alpha, beta);
+        self.tt
+            .store(key, depth, bound, score, chosen.map(|(pos, _)| pos));
+    }
+}
+
+enum Probe {
+    Cutoff(i32, Option<(Pos, Board)>),
+    Miss(Option<Pos>),
+}
+
+fn end_search(
+    evaluator: &impl Evaluate,
+    board: &Board,
+    depth: u32,
+    (alpha, beta): (i32, i32),
+    ctx: &mut SearchContext<'_>,
+) -> (i32, Option<(Pos, Board)>) {
+    match ctx.algorithm {
+        SearchAlgorithm::AlphaBeta => {
+            alpha_beta::<_, true>(evaluator, board, depth, (alpha, beta), false, ctx)
+        }
+        SearchAlgorithm::NegaScout => {
+            nega_scout::<_, true>(evaluator, board, depth, (alpha, beta), false, ctx)
+        }
+    }
+}
+
+fn mid_search(
+    evaluator: &impl Evaluate,
+    board: &Board,
+    depth: u32,
+    ctx: &mut SearchContext<'_>,
+) -> (i32, Option<(Pos, Board)>) {
+    let window = (-i32::MAX, i32::MAX);
+    match ctx.algorithm {
+        SearchAlgorithm::AlphaBeta => {
+            alpha_beta::<_, false>(evaluator, board, depth, window, false, ctx)
+        }
+        SearchAlgorithm::NegaScout => {
+            nega_scout::<_, false>(evaluator, board, depth, window, false, ctx)
         }
     }
 }
@@ -85,65 +398,235 @@ fn alpha_beta<E, const END_SEARCH: bool>(
     depth: u32,
     (mut alpha, beta): (i32, i32),
     in_pass: bool,
-    visited_nodes: &mut u32,
+    ctx: &mut SearchContext<'_>,
 ) -> (i32, Option<(Pos, Board)>)
 where
     E: Evaluate,
 {
     let alpha_beta = alpha_beta::<E, END_SEARCH>;
 
+    let ply = ctx.ply;
+    if ctx.should_abort() {
+        return (0, None);
+    }
+
     if depth == 0 {
-        *visited_nodes += 1;
+        ctx.visited_nodes += 1;
+        ctx.line_mut(ply).clear();
         let game_over = END_SEARCH;
         return (evaluator.evaluate(board, game_over), None);
     }
 
-    let mut has_candidate = false;
+    let key = board.hash64();
+    let tt_move = match ctx.probe(key, board, depth, (alpha, beta)) {
+        Probe::Cutoff(score, chosen) => return (score.clamp(alpha, beta), chosen),
+        Probe::Miss(tt_move) => tt_move,
+    };
+
+    let mut moves = board.all_flipped().collect::<Vec<_>>();
+    if ctx.move_ordering {
+        order_moves::<E, END_SEARCH>(evaluator, board, depth, tt_move, &mut moves);
+    }
+
+    let alpha0 = alpha;
     let mut chosen = None;
-    for (pos, flipped) in board.all_flipped() {
-        has_candidate = true;
-        let value = -alpha_beta(
-            evaluator,
-            &flipped,
-            depth - 1,
-            (-beta, -alpha),
-            false,
-            visited_nodes,
-        )
-        .0;
+    ctx.line_mut(ply).clear();
+    for &(pos, flipped) in &moves {
+        ctx.ply = ply + 1;
+        let value = -alpha_beta(evaluator, &flipped, depth - 1, (-beta, -alpha), false, ctx).0;
         if value > alpha {
             alpha = value;
-            chosen = Some((pos, flipped, value));
+            chosen = Some((pos, flipped));
+            ctx.update_line(ply, Move::Put(pos));
             if alpha >= beta {
-                return (beta, None);
+                alpha = beta;
+                break;
             }
         }
     }
 
-    if let Some((pos, flipped, score)) = chosen {
-        return (score, Some((pos, flipped)));
+    let score = if !moves.is_empty() {
+        alpha
+    } else if in_pass {
+        ctx.visited_nodes += 1;
+        evaluator.evaluate(board, true)
+    } else {
+        ctx.ply = ply + 1;
+        let score = -alpha_beta(
+            evaluator,
+            &board.reverse(),
+            depth,
+            (-beta, -alpha),
+            true,
+            ctx,
+        )
+        .0;
+        ctx.update_line(ply, Move::Pass);
+        score
+    };
+
+    if ctx.aborted {
+        return (0, None);
     }
-    if has_candidate {
-        return (alpha, None);
+    ctx.store(key, depth, score, (alpha0, beta), chosen);
+    (score, chosen)
+}
+
+fn nega_scout<E, const END_SEARCH: bool>(
+    evaluator: &E,
+    board: &Board,
+    depth: u32,
+    (mut alpha, beta): (i32, i32),
+    in_pass: bool,
+    ctx: &mut SearchContext<'_>,
+) -> (i32, Option<(Pos, Board)>)
+where
+    E: Evaluate,
+{
+    let nega_scout = nega_scout::<E, END_SEARCH>;
+
+    let ply = ctx.ply;
+    if ctx.should_abort() {
+        return (0, None);
     }
 
-    if in_pass {
-        *visited_nodes += 1;
-        return (evaluator.evaluate(board, true), None);
+    if depth == 0 {
+        ctx.visited_nodes += 1;
+        ctx.line_mut(ply).clear();
+        let game_over = END_SEARCH;
+        return (evaluator.evaluate(board, game_over), None);
     }
 
-    (
-        -alpha_beta(
+    let key = board.hash64();
+    let tt_move = match ctx.probe(key, board, depth, (alpha, beta)) {
+        Probe::Cutoff(score, chosen) => return (score, chosen),
+        Probe::Miss(tt_move) => tt_move,
+    };
+
+    let mut moves = board.all_flipped().collect::<Vec<_>>();
+    if ctx.move_ordering {
+        order_moves::<E, END_SEARCH>(evaluator, board, depth, tt_move, &mut moves);
+    }
+
+    let alpha0 = alpha;
+    let mut best = -i32::MAX;
+    let mut chosen = None;
+    ctx.line_mut(ply).clear();
+    for (i, &(pos, flipped)) in moves.iter().enumerate() {
+        let mut value;
+        if i == 0 {
+            ctx.ply = ply + 1;
+            value = -nega_scout(evaluator, &flipped, depth - 1, (-beta, -alpha), false, ctx).0;
+        } else {
+            // prove that the move is not better than the current best with a null window
+            ctx.ply = ply + 1;
+            value = -nega_scout(
+                evaluator,
+                &flipped,
+                depth - 1,
+                (-alpha - 1, -alpha),
+                false,
+                ctx,
+            )
+            .0;
+            if alpha < value && value < beta {
+                // the window stays open one below the proven bound: failing low at the bound
+                // itself would leave the child at a cutoff, without a principal variation
+                ctx.ply = ply + 1;
+                value = -nega_scout(
+                    evaluator,
+                    &flipped,
+                    depth - 1,
+                    (-beta, -(value - 1)),
+                    false,
+                    ctx,
+                )
+                .0;
+            }
+        }
+        best = best.max(value);
+        if value > alpha {
+            alpha = value;
+            chosen = Some((pos, flipped));
+            ctx.update_line(ply, Move::Put(pos));
+            if alpha >= beta {
+                break;
+            }
+        }
+    }
+
+    let score = if !moves.is_empty() {
+        best
+    } else if in_pass {
+        ctx.visited_nodes += 1;
+        evaluator.evaluate(board, true)
+    } else {
+        ctx.ply = ply + 1;
+        let score = -nega_scout(
             evaluator,
             &board.reverse(),
             depth,
             (-beta, -alpha),
             true,
-            visited_nodes,
+            ctx,
         )
-        .0,
-        None,
-    )
+        .0;
+        ctx.update_line(ply, Move::Pass);
+        score
+    };
+
+    if ctx.aborted {
+        return (0, None);
+    }
+    ctx.store(key, depth, score, (alpha0, beta), chosen);
+    (score, chosen)
+}
+
+// ordering moves near the leaves costs more than the cutoffs it gains
+const MID_ORDER_DEPTH: u32 = 2;
+const END_ORDER_DEPTH: u32 = 5;
+
+fn order_moves<E, const END_SEARCH: bool>(
+    evaluator: &E,
+    board: &Board,
+    depth: u32,
+    tt_move: Option<Pos>,
+    moves: &mut [(Pos, Board)],
+) where
+    E: Evaluate,
+{
+    // the best move of the table comes from the previous iteration or a transposition
+    let moves = match tt_move.and_then(|best| moves.iter().position(|&(pos, _)| pos == best)) {
+        Some(idx) => {
+            moves[..=idx].rotate_right(1);
+            &mut moves[1..]
+        }
+        None => moves,
+    };
+
+    if END_SEARCH {
+        if depth < END_ORDER_DEPTH {
+            return;
+        }
+        // fastest-first, then moves into regions with an odd number of empty cells
+        let odd_regions = board
+            .quadrant_empty_cells()
+            .into_iter()
+            .filter(|quadrant| quadrant.count() % 2 == 1)
+            .fold(PosSet::EMPTY, |acc, quadrant| acc | quadrant);
+        moves.sort_by_cached_key(|(pos, flipped)| {
+            (
+                flipped.flip_candidates().count(),
+                !odd_regions.contains(pos),
+            )
+        });
+    } else {
+        if depth < MID_ORDER_DEPTH {
+            return;
+        }
+        // the boards after the moves are seen from the opponent
+        moves.sort_by_cached_key(|(_pos, flipped)| evaluator.evaluate(flipped, false));
+    }
 }
 
 #[cfg(test)]
@@ -220,23 +703,30 @@ mod tests {
 
     #[test]
     fn comp_com() {
-        let alpha_beta = alpha_beta::<_, false>;
         let nega_max = nega_max::<_, false>;
         let evaluator = DummyEvaluator(CountEvaluator::new());
         let depth = 3;
+        let end_depth = 8;
 
-        let ab = |board| {
-            let mut visited_nodes = 0;
-            let pos = alpha_beta(
-                &evaluator,
-                &board,
-                depth,
-                (-i32::MAX, i32::MAX),
-                false,
-                &mut visited_nodes,
-            );
-            (visited_nodes, pos)
+        let search = |board: Board, algorithm, enhanced: bool| {
+            let tt_size = if enhanced { DEFAULT_TT_SIZE } else { 0 };
+            let mut tt = TranspositionTable::with_size(tt_size);
+            let mut ctx = SearchContext::new(&mut tt, enhanced, algorithm, None);
+            let pos = mid_search(&evaluator, &board, depth, &mut ctx);
+            let visited_nodes = ctx.visited_nodes;
+
+            let left = board.count_disk(None);
+            let mut tt = TranspositionTable::with_size(tt_size);
+            let mut ctx = SearchContext::new(&mut tt, enhanced, algorithm, None);
+            let window = (-i32::MAX, i32::MAX);
+            let end =
+                (left <= end_depth).then(|| end_search(&evaluator, &board, left, window, &mut ctx));
+            (visited_nodes, pos, end)
         };
+        let ab = |board| search(board, SearchAlgorithm::AlphaBeta, true);
+        let ns = |board| search(board, SearchAlgorithm::NegaScout, true);
+        // without the table and move ordering, moves are tried in the same order as nega_max
+        let plain = |board| search(board, SearchAlgorithm::AlphaBeta, false);
         let nb = |board| {
             let mut visited_nodes = 0;
             let pos = nega_max(&evaluator, &board, depth, false, &mut visited_nodes);
@@ -246,10 +736,28 @@ mod tests {
         let mut board = Board::new();
         let mut in_pass = false;
         loop {
-            let (alpha_nodes, alpha_pos) = ab(board);
+            let (alpha_nodes, alpha_pos, alpha_end) = ab(board);
+            let (_, scout_pos, scout_end) = ns(board);
+            let (plain_nodes, plain_pos, _) = plain(board);
             let (nega_nodes, nega_pos) = nb(board);
+            assert!(plain_nodes <= nega_nodes);
             assert!(alpha_nodes <= nega_nodes);
-            assert_eq!(alpha_pos, nega_pos);
+            assert_eq!(plain_pos, nega_pos);
+            // both searches try moves in the same order, so the first best move is chosen
+            assert_eq!(scout_pos, alpha_pos);
+            assert_eq!(scout_end, alpha_end);
+            if let Some((end_score, _)) = alpha_end {
+                let left = board.count_disk(None);
+                let nega_end = super::nega_max::<_, true>(&evaluator, &board, left, false, &mut 0);
+                assert_eq!(end_score, nega_end.0);
+            }
+            // moves of the same score may be chosen differently, as they are tried in another order
+            assert_eq!(alpha_pos.0, nega_pos.0);
+            if let Some((_pos, flipped)) = alpha_pos.1 {
+                let child = nega_max(&evaluator, &flipped, depth - 1, false, &mut 0);
+                assert_eq!(-child.0, alpha_pos.0);
+            }
+            assert_eq!(alpha_pos.1.is_some(), nega_pos.1.is_some());
             match alpha_pos.1 {
                 Some((_pos, flipped)) => {
                     board = flipped;
@@ -263,4 +771,156 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn transposition_table() {
+        let evaluator = CountEvaluator::new();
+
+        let mut board = Board::new();
+        while board.count_disk(None) > 14 {
+            board = match board.all_flipped().next() {
+                Some((_pos, flipped)) => flipped,
+                None => board.reverse(),
+            };
+        }
+
+        for (mid_depth, exact_depth) in [(6, 0), (0, 64)] {
+            let with_tt = Com::new(mid_depth, 0, exact_depth).next_move(&evaluator, &board);
+            let without_tt = Com::new(mid_depth, 0, exact_depth)
+                .with_tt_size(0)
+                .next_move(&evaluator, &board);
+            assert_eq!(with_tt.score, without_tt.score);
+            assert_eq!(with_tt.chosen, without_tt.chosen);
+            assert!(with_tt.visited_nodes < without_tt.visited_nodes);
+            assert!(with_tt.tt_cutoffs > 0);
+            assert!(with_tt.tt_hits >= with_tt.tt_cutoffs);
+            assert_eq!(without_tt.tt_hits, 0);
+        }
+
+        // entries kept from the previous search are reused
+        for (mid_depth, exact_depth) in [(6, 0), (0, 64)] {
+            let com = Com::new(mid_depth, 0, exact_depth);
+            let first = com.next_move(&evaluator, &board);
+            let second = com.next_move(&evaluator, &board);
+            assert_eq!(second.score, first.score);
+            assert_eq!(second.chosen, first.chosen);
+            // the line below the cutoff at the root ends where the table lost an entry
+            assert!(first.pv.starts_with(&second.pv));
+            assert_eq!(second.pv.first(), first.pv.first());
+            assert!(second.visited_nodes < first.visited_nodes);
+
+            let next = first.chosen.unwrap().1;
+            let reused = com.next_move(&evaluator, &next);
+            let fresh = Com::new(mid_depth, 0, exact_depth).next_move(&evaluator, &next);
+            assert_eq!(reused.score, fresh.score);
+            assert!(reused.visited_nodes < fresh.visited_nodes);
+        }
+    }
+
+    #[test]
+    fn move_ordering() {
+        let evaluator = DummyEvaluator(CountEvaluator::new());
+
+        let mut ordered_nodes = 0;
+        let mut unordered_nodes = 0;
+        let mut board = Board::new();
+        while board.count_disk(None) > 14 {
+            let ordered = Com::new(5, 0, 14).next_move(&evaluator, &board);
+            let unordered = Com::new(5, 0, 14)
+                .with_move_ordering(false)
+                .next_move(&evaluator, &board);
+            assert_eq!(ordered.score, unordered.score);
+            ordered_nodes += ordered.visited_nodes;
+            unordered_nodes += unordered.visited_nodes;
+            board = match board.all_flipped().next() {
+                Some((_pos, flipped)) => flipped,
+                None => board.reverse(),
+            };
+        }
+
+        let ordered = Com::new(0, 0, 64).next_move(&evaluator, &board);
+        let unordered = Com::new(0, 0, 64)
+            .with_move_ordering(false)
+            .next_move(&evaluator, &board);
+        assert_eq!(ordered.score, unordered.score);
+        assert!(ordered.visited_nodes < unordered.visited_nodes);
+
+        assert!(ordered_nodes < unordered_nodes);
+    }
+
+    #[test]
+    fn principal_variation() {
+        let evaluator = DummyEvaluator(CountEvaluator::new());
+
+        // the leaf at the end of the line has the score of the search
+        let follow = |board: &Board, pv: &[Move]| {
+            let mut board = *board;
+            let mut sign = 1;
+            for mv in pv {
+                board = match mv {
+                    Move::Put(pos) => board.flipped(*pos).unwrap(),
+                    Move::Pass => {
+                        assert!(!board.can_play());
+                        board.reverse()
+                    }
+                };
+                sign = -sign;
+            }
+            (board, sign)
+        };
+
+        let board = Board::new();
+        for algorithm in [SearchAlgorithm::AlphaBeta, SearchAlgorithm::NegaScout] {
+            let next_move = Com::new(4, 0, 0)
+                .with_algorithm(algorithm)
+                .with_tt_size(0)
+                .next_move(&evaluator, &board);
+            assert_eq!(next_move.pv.len(), 4);
+            assert_eq!(next_move.pv[0], Move::Put(next_move.chosen.unwrap().0));
+            let (leaf, sign) = follow(&board, &next_move.pv);
+            assert_eq!(sign * evaluator.evaluate(&leaf, false), next_move.score);
+        }
+
+        let mut board = Board::new();
+        while board.count_disk(None) > 10 {
+            board = match board.all_flipped().next() {
+                Some((_pos, flipped)) => flipped,
+                None => board.reverse(),
+            };
+        }
+        for algorithm in [SearchAlgorithm::AlphaBeta, SearchAlgorithm::NegaScout] {
+            let next_move = Com::new(0, 0, 64)
+                .with_algorithm(algorithm)
+                .next_move(&evaluator, &board);
+            assert_eq!(next_move.pv[0], Move::Put(next_move.chosen.unwrap().0));
+            let (leaf, sign) = follow(&board, &next_move.pv);
+            assert!(!leaf.can_play() && !leaf.reverse().can_play());
+            assert_eq!(sign * evaluator.evaluate(&leaf, true), next_move.score);
+        }
+    }
+
+    #[test]
+    fn next_move_within() {
+        let evaluator = CountEvaluator::new();
+
+        let budget = Duration::from_millis(50);
+        let start = Instant::now();
+        let next_move = Com::new(0, 0, 0).next_move_within(&evaluator, &Board::new(), budget);
+        assert!(next_move.chosen.is_some());
+        assert!(start.elapsed() < budget * 10);
+
+        // with enough time the endgame is solved exactly
+        let mut board = Board::new();
+        while board.count_disk(None) > 12 {
+            board = match board.all_flipped().next() {
+                Some((_pos, flipped)) => flipped,
+                None => board.reverse(),
+            };
+        }
+        let exact = Com::new(0, 0, 64).next_move(&evaluator, &board);
+        let within =
+            Com::new(0, 0, 0).next_move_within(&evaluator, &board, Duration::from_secs(60));
+        assert_eq!(within.score, exact.score);
+        assert_eq!(within.chosen, exact.chosen);
+    }
 }
@@ -0,0 +1,217 @@
+use reversi_core::Pos;
+use std::mem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+impl Bound {
+    pub(super) fn new(score: i32, alpha: i32, beta: i32) -> Self {
+        if score <= alpha {
+            Bound::Upper
+        } else if score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Entry {
+    key: u64,
+    generation: u32,
+    depth: u32,
+    bound: Bound,
+    score: i32,
+    best: Option<Pos>,
+}
+
+impl Entry {
+    pub(super) fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    pub(super) fn bound(&self) -> Bound {
+        self.bound
+    }
+
+    pub(super) fn score(&self) -> i32 {
+        self.score
+    }
+
+    pub(super) fn best(&self) -> Option<Pos> {
+        self.best
+    }
+}
+
+// mid-game scores depend on the evaluator and the depth, so they are not bounds of the
+// final result and the other way around
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Scores {
+    Mid,
+    End,
+}
+
+#[derive(Debug)]
+pub(super) struct TranspositionTable {
+    entries: Vec<Option<Entry>>,
+    scores: Scores,
+    generation: u32,
+    // entries stored before this generation hold the other kind of scores
+    valid_from: u32,
+}
+
+// the number of entries is rounded down to a power of two, so that a slot is just a mask of the key
+fn entry_count(bytes: usize) -> usize {
+    let len = bytes / mem::size_of::<Option<Entry>>();
+    if len == 0 {
+        0
+    } else {
+        1 << (usize::BITS - 1 - len.leading_zeros())
+    }
+}
+
+impl TranspositionTable {
+    pub(super) fn with_size(bytes: usize) -> Self {
+        Self {
+            entries: vec![None; entry_count(bytes)],
+            scores: Scores::Mid,
+            generation: 0,
+            valid_from: 0,
+        }
+    }
+
+    pub(super) fn resize(&mut self, bytes: usize) {
+        let len = entry_count(bytes);
+        if len != self.entries.len() {
+            self.entries = vec![None; len];
+        }
+    }
+
+    // entries of earlier searches are still found but replaced first, and entries with the
+    // other kind of scores are ignored, so that the table is never cleared
+    pub(super) fn new_search(&mut self, scores: Scores) {
+        self.generation = self.generation.wrapping_add(1);
+        if scores != self.scores {
+            self.scores = scores;
+            self.valid_from = self.generation;
+        }
+    }
+
+    fn is_valid(&self, entry: &Entry) -> bool {
+        entry.generation.wrapping_sub(self.valid_from)
+            <= self.generation.wrapping_sub(self.valid_from)
+    }
+
+    fn slot(&self, key: u64) -> Option<usize> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        Some(key as usize & (self.entries.len() - 1))
+    }
+
+    pub(super) fn probe(&self, key: u64) -> Option<&Entry> {
+        let slot = self.slot(key)?;
+        self.entries[slot]
+            .as_ref()
+            .filter(|entry| entry.key == key && self.is_valid(entry))
+    }
+
+    pub(super) fn store(
+        &mut self,
+        key: u64,
+        depth: u32,
+        bound: Bound,
+        score: i32,
+        best: Option<Pos>,
+    ) {
+        let slot = match self.slot(key) {
+            Some(slot) => slot,
+            None => return,
+        };
+        let entry = &mut self.entries[slot];
+        let replace = match entry {
+            None => true,
+            Some(old) if old.generation != self.generation => true,
+            // a bound does not overwrite the exact score of the same position, which the
+            // principal variation is rebuilt from
+            Some(old) if old.key == key => {
+                bound == Bound::Exact || old.bound != Bound::Exact || old.depth < depth
+            }
+            Some(old) => old.depth <= depth,
+        };
+        if replace {
+            *entry = Some(Entry {
+                key,
+                generation: self.generation,
+                depth,
+                bound,
+                score,
+                best,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_by_depth() {
+        let mut tt = TranspositionTable::with_size(1000);
+        let len = tt.entries.len();
+        assert!(len.is_power_of_two());
+        assert!(len * mem::size_of::<Option<Entry>>() <= 1000);
+
+        let key = 3;
+        let other = key + len as u64;
+        tt.store(key, 5, Bound::Exact, 10, None);
+        assert_eq!(tt.probe(key).unwrap().score(), 10);
+        assert!(tt.probe(other).is_none());
+
+        // a shallower entry does not evict a deeper one
+        tt.store(other, 4, Bound::Lower, 20, None);
+        assert_eq!(tt.probe(key).unwrap().score(), 10);
+        assert!(tt.probe(other).is_none());
+
+        tt.store(other, 5, Bound::Upper, 30, None);
+        assert!(tt.probe(key).is_none());
+        assert_eq!(tt.probe(other).unwrap().bound(), Bound::Upper);
+
+        // the same position is updated unless a bound would replace an exact score
+        tt.store(other, 1, Bound::Exact, 40, None);
+        assert_eq!(tt.probe(other).unwrap().depth(), 1);
+        tt.store(other, 1, Bound::Lower, 45, None);
+        assert_eq!(tt.probe(other).unwrap().bound(), Bound::Exact);
+        tt.store(other, 2, Bound::Lower, 45, None);
+        assert_eq!(tt.probe(other).unwrap().bound(), Bound::Lower);
+
+        // entries of an earlier search are found, but replaced by any entry
+        tt.new_search(Scores::Mid);
+        assert_eq!(tt.probe(other).unwrap().depth(), 2);
+        tt.store(key, 0, Bound::Upper, 50, None);
+        assert_eq!(tt.probe(key).unwrap().score(), 50);
+        assert!(tt.probe(other).is_none());
+
+        // entries with the other kind of scores are not found
+        tt.new_search(Scores::End);
+        assert!(tt.probe(key).is_none());
+        tt.store(key, 0, Bound::Upper, 60, None);
+        tt.new_search(Scores::End);
+        assert_eq!(tt.probe(key).unwrap().score(), 60);
+
+        tt.resize(1000);
+        assert_eq!(tt.probe(key).unwrap().score(), 60);
+        tt.resize(0);
+        assert!(tt.probe(key).is_none());
+
+        let mut empty = TranspositionTable::with_size(0);
+        empty.store(key, 5, Bound::Exact, 10, None);
+        assert!(empty.probe(key).is_none());
+    }
+}
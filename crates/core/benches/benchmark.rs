@@ -1,5 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use reversi_core::{Board, Disk, Pos};
+use std::str::FromStr;
 
 fn board_flipped(c: &mut Criterion) {
     c.bench_function("flipped initial", |b| {
@@ -18,20 +19,18 @@ fn board_flipped(c: &mut Criterion) {
         // o x x x x x x o      o O x O x O x o
         // o x x x x x x o      o x x O x x O o
         // o o o o o o o o      o o o o o o o o
-        let mut board = Board::empty();
-        for x in 0..Board::SIZE {
-            for y in 0..Board::SIZE {
-                let p = Pos::from_xy(x, y).unwrap();
-                board.set_disk(p, Disk::Mine);
-            }
-        }
-        for x in 1..(Board::SIZE - 1) {
-            for y in 1..(Board::SIZE - 1) {
-                let p = Pos::from_xy(x, y).unwrap();
-                board.set_disk(p, Disk::Others);
-            }
-        }
-        board.unset_disk(Pos::D4);
+        #[rustfmt::skip]
+        let board = Board::from_str(concat!(
+            "XXXXXXXX",
+            "XOOOOOOX",
+            "XOOOOOOX",
+            "XOO-OOOX",
+            "XOOOOOOX",
+            "XOOOOOOX",
+            "XOOOOOOX",
+            "XXXXXXXX",
+        ))
+        .unwrap();
         assert_eq!(board.count_disk(Some(Disk::Mine)), 28);
         assert_eq!(board.count_disk(Some(Disk::Others)), 35);
         assert_eq!(board.count_disk(None), 1);
@@ -58,19 +57,18 @@ fn board_all_flipped(c: &mut Criterion) {
     });
 
     c.bench_function("all_flipped many", |b| {
-        let mut board = Board::empty();
-        for x in 1..(Board::SIZE - 1) {
-            for y in 1..(Board::SIZE - 1) {
-                let p = Pos::from_xy(x, y).unwrap();
-                board.set_disk(p, Disk::Others);
-            }
-        }
-        for x in 2..(Board::SIZE - 2) {
-            for y in 2..(Board::SIZE - 2) {
-                let p = Pos::from_xy(x, y).unwrap();
-                board.set_disk(p, Disk::Mine);
-            }
-        }
+        #[rustfmt::skip]
+        let board = Board::from_str(concat!(
+            "--------",
+            "-OOOOOO-",
+            "-OXXXXO-",
+            "-OXXXXO-",
+            "-OXXXXO-",
+            "-OXXXXO-",
+            "-OOOOOO-",
+            "--------",
+        ))
+        .unwrap();
         b.iter(|| {
             let board = black_box(board);
             for (pos, board) in board.all_flipped() {
@@ -80,5 +78,52 @@ fn board_all_flipped(c: &mut Criterion) {
     });
 }
 
+#[cfg(feature = "simd")]
+fn board_simd(c: &mut Criterion) {
+    #[rustfmt::skip]
+    let board = Board::from_str(concat!(
+        "--XXXXX-",
+        "-OOOXX-O",
+        "OOOXXOX-",
+        "OXOXOXXO",
+        "XXXOXXX-",
+        "-XOXOXX-",
+        "XXXOOO--",
+        "OOOOO---",
+    ))
+    .unwrap();
+
+    let mut group = c.benchmark_group("flip_candidates");
+    group.bench_function("portable", |b| {
+        b.iter(|| black_box(black_box(board).flip_candidates_portable()));
+    });
+    group.bench_function("simd", |b| {
+        b.iter(|| black_box(black_box(board).flip_candidates()));
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("flipped_set");
+    group.bench_function("portable", |b| {
+        b.iter(|| {
+            let board = black_box(board);
+            for pos in board.empty_cells() {
+                black_box(board.flipped_set_portable(pos));
+            }
+        });
+    });
+    group.bench_function("simd", |b| {
+        b.iter(|| {
+            let board = black_box(board);
+            for pos in board.empty_cells() {
+                black_box(board.flipped_set(pos));
+            }
+        });
+    });
+    group.finish();
+}
+
+#[cfg(not(feature = "simd"))]
 criterion_group!(benches, board_flipped, board_all_flipped);
+#[cfg(feature = "simd")]
+criterion_group!(benches, board_flipped, board_all_flipped, board_simd);
 criterion_main!(benches);
@@ -1,10 +1,17 @@
-pub use self::{color::*, pos::*};
-use std::iter::FusedIterator;
+pub use self::{color::*, pos::*, transform::*};
+use std::{fmt, iter::FusedIterator, str::FromStr};
 
+mod axis;
 mod color;
+mod hash;
+mod metrics;
 mod pos;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
+mod stable;
+mod transform;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Board {
     mine_disks: PosSet,
     others_disks: PosSet,
@@ -22,6 +29,50 @@ impl Default for Board {
     }
 }
 
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for disk in self.disks() {
+            let ch = match disk {
+                Some(Disk::Mine) => 'X',
+                Some(Disk::Others) => 'O',
+                None => '-',
+            };
+            write!(f, "{}", ch)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseBoardError {
+    #[error("invalid board length {0}, expected 64")]
+    InvalidLength(usize),
+    #[error("invalid disk `{0}` found in string")]
+    InvalidDisk(char),
+}
+
+impl FromStr for Board {
+    type Err = ParseBoardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let len = s.chars().count();
+        if len != (Board::SIZE * Board::SIZE) as usize {
+            return Err(Self::Err::InvalidLength(len));
+        }
+
+        let mut board = Board::empty();
+        for (pos, ch) in Pos::iter_all().zip(s.chars()) {
+            match ch {
+                'X' | 'x' => board.set_disk(pos, Disk::Mine),
+                'O' | 'o' => board.set_disk(pos, Disk::Others),
+                '-' => {}
+                _ => return Err(Self::Err::InvalidDisk(ch)),
+            }
+        }
+        Ok(board)
+    }
+}
+
 impl Board {
     pub const SIZE: i8 = 8;
 
@@ -39,6 +90,22 @@ impl Board {
         }
     }
 
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_disk_sets(mine_disks: PosSet, others_disks: PosSet) -> Option<Self> {
+        if !(mine_disks & others_disks).is_empty() {
+            return None;
+        }
+        Some(Self {
+            mine_disks,
+            others_disks,
+        })
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn disk_sets(&self) -> (PosSet, PosSet) {
+        (self.mine_disks, self.others_disks)
+    }
+
     pub fn disks(&self) -> Disks {
         Disks::new(self)
     }
@@ -98,6 +165,14 @@ impl Board {
     }
 
     fn flipped_set_unchecked(&self, pos: Pos) -> PosSet {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if let Some(flipped) = simd::flipped_set_unchecked(self, pos) {
+            return flipped;
+        }
+        self.flipped_set_unchecked_portable(pos)
+    }
+
+    fn flipped_set_unchecked_portable(&self, pos: Pos) -> PosSet {
         debug_assert!(!(self.mine_disks | self.others_disks).contains(&pos));
         let top_bottom_mask = PosSet::ALL;
         let left_right_mask = !(PosSet::new()
@@ -180,6 +255,27 @@ impl Board {
     }
 
     pub fn flip_candidates(&self) -> PosSet {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if let Some(candidates) = simd::flip_candidates(self) {
+            return candidates;
+        }
+        self.flip_candidates_portable()
+    }
+
+    #[doc(hidden)]
+    pub fn flipped_set_portable(&self, pos: Pos) -> Option<PosSet> {
+        if (self.mine_disks | self.others_disks).contains(&pos) {
+            return None;
+        }
+        let flipped = self.flipped_set_unchecked_portable(pos);
+        if flipped.is_empty() {
+            return None;
+        }
+        Some(flipped)
+    }
+
+    #[doc(hidden)]
+    pub fn flip_candidates_portable(&self) -> PosSet {
         let top_bottom_mask = PosSet::ALL;
         let left_right_mask = !(PosSet::new()
             | (Pos::A1 | Pos::A2 | Pos::A3 | Pos::A4 | Pos::A5 | Pos::A6 | Pos::A7 | Pos::A8)
@@ -223,6 +319,21 @@ impl Board {
         !self.flip_candidates().is_empty()
     }
 
+    pub fn transform(&self, transform: Transform) -> Self {
+        Self {
+            mine_disks: self.mine_disks.transform(transform),
+            others_disks: self.others_disks.transform(transform),
+        }
+    }
+
+    pub fn canonical(&self) -> (Self, Transform) {
+        Transform::ALL
+            .into_iter()
+            .map(|transform| (self.transform(transform), transform))
+            .min_by_key(|(board, _)| *board)
+            .unwrap()
+    }
+
     pub fn from_pattern_index(pattern: &[Pos], index: u16) -> Self {
         let mut board = Self::empty();
         let mut n = index;
@@ -450,4 +561,69 @@ mod tests {
         board = board.reverse();
         assert!(board.can_play());
     }
+
+    #[test]
+    fn transform() {
+        let mut board = Board::new();
+        for pos in [Pos::F5, Pos::F6, Pos::E6, Pos::F4, Pos::G5] {
+            board = board.flipped(pos).unwrap();
+        }
+
+        let (canonical, _) = board.canonical();
+        for transform in Transform::ALL {
+            let transformed = board.transform(transform);
+            assert_eq!(
+                transformed.count_disk(Some(Disk::Mine)),
+                board.count_disk(Some(Disk::Mine))
+            );
+            assert_eq!(
+                transformed.flip_candidates(),
+                board.flip_candidates().transform(transform)
+            );
+            for pos in Pos::iter_all() {
+                assert_eq!(
+                    transformed.get_disk(pos.transform(transform)),
+                    board.get_disk(pos)
+                );
+            }
+            assert_eq!(transformed.transform(transform.inverse()), board);
+
+            let (transformed_canonical, t) = transformed.canonical();
+            assert_eq!(transformed_canonical, canonical);
+            assert_eq!(transformed.transform(t), canonical);
+        }
+
+        // the initial position is symmetric under 4 of the 8 transforms
+        let initial = Board::new();
+        assert_eq!(
+            Transform::ALL
+                .into_iter()
+                .filter(|t| initial.transform(*t) == initial)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn display_from_str() {
+        let s = "---------------------------OX------XO---------------------------";
+        assert_eq!(Board::new().to_string(), s);
+        assert_eq!(Board::from_str(s).unwrap(), Board::new());
+        assert_eq!(
+            Board::from_str(&s.to_ascii_lowercase()).unwrap(),
+            Board::new()
+        );
+
+        let board = Board::new().flipped(Pos::F5).unwrap();
+        assert_eq!(Board::from_str(&board.to_string()).unwrap(), board);
+
+        assert!(matches!(
+            Board::from_str(&s[1..]),
+            Err(ParseBoardError::InvalidLength(63))
+        ));
+        assert!(matches!(
+            Board::from_str(&s.replace('X', "*")),
+            Err(ParseBoardError::InvalidDisk('*'))
+        ));
+    }
 }
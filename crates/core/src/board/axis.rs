@@ -0,0 +1,92 @@
+use super::{Board, Pos, PosSet};
+
+const COLUMN_A: PosSet = PosSet::from_slice(&[
+    Pos::A1,
+    Pos::A2,
+    Pos::A3,
+    Pos::A4,
+    Pos::A5,
+    Pos::A6,
+    Pos::A7,
+    Pos::A8,
+]);
+const COLUMN_H: PosSet = PosSet::from_slice(&[
+    Pos::H1,
+    Pos::H2,
+    Pos::H3,
+    Pos::H4,
+    Pos::H5,
+    Pos::H6,
+    Pos::H7,
+    Pos::H8,
+]);
+const ROW_1: PosSet = PosSet::from_slice(&[
+    Pos::A1,
+    Pos::B1,
+    Pos::C1,
+    Pos::D1,
+    Pos::E1,
+    Pos::F1,
+    Pos::G1,
+    Pos::H1,
+]);
+const ROW_8: PosSet = PosSet::from_slice(&[
+    Pos::A8,
+    Pos::B8,
+    Pos::C8,
+    Pos::D8,
+    Pos::E8,
+    Pos::F8,
+    Pos::G8,
+    Pos::H8,
+]);
+
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Axis {
+    Horizontal,
+    Vertical,
+    Diagonal,
+    AntiDiagonal,
+}
+
+impl Axis {
+    pub(super) const ALL: [Self; 4] = [
+        Self::Horizontal,
+        Self::Vertical,
+        Self::Diagonal,
+        Self::AntiDiagonal,
+    ];
+
+    pub(super) fn neighbors(&self, set: PosSet) -> PosSet {
+        let not_a = set & !COLUMN_A;
+        let not_h = set & !COLUMN_H;
+        match self {
+            Self::Horizontal => (not_h << 1) | (not_a >> 1),
+            Self::Vertical => (set << 8) | (set >> 8),
+            Self::Diagonal => (not_h << 9) | (not_a >> 9),
+            Self::AntiDiagonal => (not_a << 7) | (not_h >> 7),
+        }
+    }
+
+    pub(super) fn all_neighbors(set: PosSet) -> PosSet {
+        Self::ALL
+            .iter()
+            .fold(PosSet::new(), |acc, axis| acc | axis.neighbors(set))
+    }
+
+    pub(super) fn edge(&self) -> PosSet {
+        match self {
+            Self::Horizontal => COLUMN_A | COLUMN_H,
+            Self::Vertical => ROW_1 | ROW_8,
+            Self::Diagonal | Self::AntiDiagonal => COLUMN_A | COLUMN_H | ROW_1 | ROW_8,
+        }
+    }
+
+    pub(super) fn full_lines(&self, empty_cells: PosSet) -> PosSet {
+        let mut not_full = empty_cells;
+        for _ in 0..(Board::SIZE - 1) {
+            not_full |= self.neighbors(not_full);
+        }
+        !not_full
+    }
+}
@@ -0,0 +1,106 @@
+use super::{Board, Pos, PosSet};
+
+const SEED: u64 = 0x5265_7665_7273_6921;
+
+const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (state, z ^ (z >> 31))
+}
+
+// `TABLE[i][b]` is the xor of the keys of the squares set in the `i`-th byte `b`.
+const TABLE: [[u64; 256]; 8] = {
+    let mut keys = [0; 64];
+    let mut state = SEED;
+    let mut i = 0;
+    while i < keys.len() {
+        let (next, key) = splitmix64(state);
+        state = next;
+        keys[i] = key;
+        i += 1;
+    }
+
+    let mut table = [[0; 256]; 8];
+    let mut i = 0;
+    while i < table.len() {
+        let mut b = 1;
+        while b < table[i].len() {
+            let bit = b.trailing_zeros() as usize;
+            table[i][b] = table[i][b & (b - 1)] ^ keys[i * 8 + bit];
+            b += 1;
+        }
+        i += 1;
+    }
+    table
+};
+
+fn hash_set(set: PosSet) -> u64 {
+    let bits = set.bits();
+    let mut hash = 0;
+    for (i, table) in TABLE.iter().enumerate() {
+        hash ^= table[((bits >> (i * 8)) & 0xff) as usize];
+    }
+    hash
+}
+
+// Others' keys are mine's rotated by 32 bits, so that swapping sides is a rotation.
+fn rotate(hash: u64) -> u64 {
+    hash.rotate_left(32)
+}
+
+impl Board {
+    /// Returns a 64-bit Zobrist hash of the board.
+    ///
+    /// The keys are generated from a fixed seed at compile time, so the hash is
+    /// stable across runs and platforms and can be stored in books and caches.
+    pub fn hash64(&self) -> u64 {
+        hash_set(self.mine_disks) ^ rotate(hash_set(self.others_disks))
+    }
+
+    /// Returns the hash of `board.flipped(pos)` from `hash == board.hash64()` and
+    /// `flipped == board.flipped_set(pos)`.
+    pub fn flipped_hash64(hash: u64, pos: Pos, flipped: PosSet) -> u64 {
+        let flipped = hash_set(flipped);
+        rotate(hash) ^ flipped ^ rotate(flipped ^ hash_set(PosSet::new() | pos))
+    }
+
+    /// Returns the hash of `board.reverse()` from `hash == board.hash64()`.
+    pub fn reversed_hash64(hash: u64) -> u64 {
+        rotate(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn stable() {
+        assert_eq!(Board::empty().hash64(), 0);
+        assert_eq!(Board::new().hash64(), 0x5dbe_6a29_e2e3_d328);
+    }
+
+    #[test]
+    fn incremental() {
+        fn check(board: &Board, hash: u64, depth: u32, seen: &mut HashMap<u64, Board>) {
+            assert_eq!(board.hash64(), hash);
+            assert_eq!(*seen.entry(hash).or_insert(*board), *board);
+            assert_eq!(Board::reversed_hash64(hash), board.reverse().hash64());
+            if depth == 0 {
+                return;
+            }
+            for (pos, flipped) in board.all_flipped() {
+                let flipped_set = board.flipped_set(pos).unwrap();
+                let flipped_hash = Board::flipped_hash64(hash, pos, flipped_set);
+                check(&flipped, flipped_hash, depth - 1, seen);
+            }
+        }
+        let board = Board::new();
+        let mut seen = HashMap::new();
+        check(&board, board.hash64(), 6, &mut seen);
+        assert!(seen.len() > 1000);
+    }
+}
@@ -0,0 +1,110 @@
+use super::{axis::Axis, Board, PosSet};
+
+impl Board {
+    pub const QUADRANTS: [PosSet; 4] = [
+        PosSet::from_bits(0x0000_0000_0f0f_0f0f),
+        PosSet::from_bits(0x0000_0000_f0f0_f0f0),
+        PosSet::from_bits(0x0f0f_0f0f_0000_0000),
+        PosSet::from_bits(0xf0f0_f0f0_0000_0000),
+    ];
+
+    pub fn empty_cells(&self) -> PosSet {
+        !(self.mine_disks | self.others_disks)
+    }
+
+    pub fn potential_mobility(&self) -> PosSet {
+        self.empty_cells() & Axis::all_neighbors(self.others_disks)
+    }
+
+    pub fn frontier_disks(&self) -> (PosSet, PosSet) {
+        let frontier = Axis::all_neighbors(self.empty_cells());
+        (self.mine_disks & frontier, self.others_disks & frontier)
+    }
+
+    pub fn quadrant_empty_cells(&self) -> [PosSet; 4] {
+        let empty_cells = self.empty_cells();
+        Self::QUADRANTS.map(|quadrant| quadrant & empty_cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::random_board, Disk, Pos};
+    use rand::prelude::*;
+
+    fn neighbors(pos: Pos) -> impl Iterator<Item = Pos> {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|&d| d != (0, 0))
+            .filter_map(move |(dx, dy)| Pos::from_xy(pos.x() + dx, pos.y() + dy))
+    }
+
+    #[test]
+    fn initial() {
+        use Pos as P;
+
+        let board = Board::new();
+        assert_eq!(
+            board.potential_mobility(),
+            PosSet::from_iter([
+                P::C3,
+                P::D3,
+                P::E3,
+                P::C4,
+                P::C5,
+                P::F4,
+                P::F5,
+                P::F6,
+                P::E6,
+                P::D6
+            ])
+        );
+        assert_eq!(
+            board.frontier_disks(),
+            (
+                PosSet::from_iter([P::E4, P::D5]),
+                PosSet::from_iter([P::D4, P::E5])
+            )
+        );
+        assert_eq!(board.quadrant_empty_cells().map(|set| set.count()), [15; 4]);
+        assert_eq!(
+            Board::QUADRANTS
+                .into_iter()
+                .fold(PosSet::new(), |acc, quadrant| acc | quadrant),
+            PosSet::ALL
+        );
+    }
+
+    #[test]
+    fn compare_with_reference() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for plies in 0..500 {
+            let board = random_board(&mut rng, plies % 60);
+
+            let mut potential_mobility = PosSet::new();
+            let mut frontier = (PosSet::new(), PosSet::new());
+            for pos in Pos::iter_all() {
+                let mut around = neighbors(pos).map(|p| board.get_disk(p));
+                match board.get_disk(pos) {
+                    None if around.any(|disk| disk == Some(Disk::Others)) => {
+                        potential_mobility |= pos
+                    }
+                    Some(Disk::Mine) if around.any(|disk| disk.is_none()) => frontier.0 |= pos,
+                    Some(Disk::Others) if around.any(|disk| disk.is_none()) => frontier.1 |= pos,
+                    _ => {}
+                }
+            }
+            assert_eq!(board.potential_mobility(), potential_mobility);
+            assert_eq!(board.frontier_disks(), frontier);
+            assert_eq!(
+                board
+                    .quadrant_empty_cells()
+                    .iter()
+                    .map(|set| set.count())
+                    .sum::<u32>(),
+                board.count_disk(None)
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::Board;
+use super::{Board, Transform};
 use crate::traits::{IterOneBits, OneBits};
 use std::{
     fmt,
@@ -45,6 +45,8 @@ pub enum ParsePosError {
     ParseInt(String, ParseIntError),
     #[error("invalid pos `{0}{1}`")]
     InvalidPos(char, i8),
+    #[error("invalid row `{0}`")]
+    InvalidRow(String),
 }
 
 impl FromStr for Pos {
@@ -55,19 +57,24 @@ impl FromStr for Pos {
         let mut cs = s.chars();
 
         let alpha = cs.next().ok_or(Self::Err::Empty).and_then(|alpha| {
-            if !alpha.is_alphabetic() {
+            if !('A'..='H').contains(&alpha) {
                 return Err(Self::Err::InvalidAlphabet(alpha));
             }
             Ok(alpha)
         })?;
-        let num = cs
-            .as_str()
+        let row = cs.as_str();
+        let num = row
             .parse::<i8>()
-            .map_err(|e| ParsePosError::ParseInt(cs.as_str().into(), e))?;
+            .map_err(|e| ParsePosError::ParseInt(row.into(), e))?;
+        // only a single digit is a row, so that `A+1` and `A01` are not read as `A1`
+        if row.len() != 1 {
+            return Err(Self::Err::InvalidRow(row.into()));
+        }
 
         let x = (alpha as u8 - b'A') as i8;
-        let y = num - 1;
-        Pos::from_xy(x, y).ok_or_else(|| Self::Err::InvalidPos(alpha, num))
+        num.checked_sub(1)
+            .and_then(|y| Pos::from_xy(x, y))
+            .ok_or_else(|| Self::Err::InvalidPos(alpha, num))
     }
 }
 
@@ -134,6 +141,10 @@ impl Pos {
     pub const fn y(&self) -> i8 {
         self.0.trailing_zeros() as i8 / Board::SIZE
     }
+
+    pub fn transform(&self, transform: Transform) -> Self {
+        Self(transform.apply(self.0))
+    }
 }
 
 #[derive(Debug)]
@@ -196,6 +207,14 @@ impl PosSet {
         }
     }
 
+    pub(crate) const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    pub(crate) const fn bits(&self) -> u64 {
+        self.0
+    }
+
     pub fn is_empty(&self) -> bool {
         self.0 == 0
     }
@@ -207,6 +226,10 @@ impl PosSet {
     pub fn contains(&self, pos: &Pos) -> bool {
         self.0 & pos.bit().0 != 0
     }
+
+    pub fn transform(&self, transform: Transform) -> Self {
+        Self(transform.apply(self.0))
+    }
 }
 
 impl std::ops::Not for PosSet {
@@ -349,6 +372,40 @@ mod tests {
         assert_eq!(Pos::from_str("A1").unwrap(), Pos::A1);
         assert_eq!(Pos::from_str("C8").unwrap(), Pos::C8);
         assert_eq!(Pos::from_str("H3").unwrap(), Pos::H3);
+        assert_eq!(Pos::from_str("h3").unwrap(), Pos::H3);
+
+        assert!(matches!(
+            Pos::from_str("I1"),
+            Err(ParsePosError::InvalidAlphabet('I'))
+        ));
+        assert!(matches!(
+            Pos::from_str("Ā1"),
+            Err(ParsePosError::InvalidAlphabet('Ā'))
+        ));
+        assert!(matches!(
+            Pos::from_str("A-128"),
+            Err(ParsePosError::InvalidRow(row)) if row == "-128"
+        ));
+        assert!(matches!(
+            Pos::from_str("A+1"),
+            Err(ParsePosError::InvalidRow(row)) if row == "+1"
+        ));
+        assert!(matches!(
+            Pos::from_str("A01"),
+            Err(ParsePosError::InvalidRow(row)) if row == "01"
+        ));
+        assert!(matches!(
+            Pos::from_str("A"),
+            Err(ParsePosError::ParseInt(..))
+        ));
+        assert!(matches!(
+            Pos::from_str("A0"),
+            Err(ParsePosError::InvalidPos('A', 0))
+        ));
+        assert!(matches!(
+            Pos::from_str("A9"),
+            Err(ParsePosError::InvalidPos('A', 9))
+        ));
     }
 
     #[test]
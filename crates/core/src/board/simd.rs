@@ -0,0 +1,114 @@
+use super::{Board, Pos, PosSet};
+use std::arch::x86_64::*;
+
+const LEFT_RIGHT_MASK: i64 = 0x7e7e_7e7e_7e7e_7e7e;
+
+pub(super) fn flip_candidates(board: &Board) -> Option<PosSet> {
+    if !is_x86_feature_detected!("avx2") {
+        return None;
+    }
+    let bits = unsafe { flip_candidates_avx2(board.mine_disks.bits(), board.others_disks.bits()) };
+    Some(PosSet::from_bits(bits))
+}
+
+pub(super) fn flipped_set_unchecked(board: &Board, pos: Pos) -> Option<PosSet> {
+    if !is_x86_feature_detected!("avx2") {
+        return None;
+    }
+    debug_assert!(!(board.mine_disks | board.others_disks).contains(&pos));
+    let pos = (PosSet::new() | pos).bits();
+    let bits = unsafe { flipped_set_avx2(board.mine_disks.bits(), board.others_disks.bits(), pos) };
+    Some(PosSet::from_bits(bits))
+}
+
+// Each 64-bit lane handles one of the four axes; shifting left and right covers both directions.
+#[target_feature(enable = "avx2")]
+unsafe fn shifts_and_masks() -> (__m256i, __m256i) {
+    let shift = _mm256_set_epi64x(7, 8, 9, 1);
+    let mask = _mm256_set_epi64x(LEFT_RIGHT_MASK, -1, LEFT_RIGHT_MASK, LEFT_RIGHT_MASK);
+    (shift, mask)
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn or_lanes(v: __m256i) -> u64 {
+    let v = _mm_or_si128(_mm256_castsi256_si128(v), _mm256_extracti128_si256::<1>(v));
+    _mm_cvtsi128_si64(_mm_or_si128(v, _mm_unpackhi_epi64(v, v))) as u64
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn flip_candidates_avx2(mine: u64, others: u64) -> u64 {
+    let (shift, mask) = shifts_and_masks();
+    let mine_v = _mm256_set1_epi64x(mine as i64);
+    let e = _mm256_and_si256(_mm256_set1_epi64x(others as i64), mask);
+
+    let mut l = _mm256_and_si256(_mm256_sllv_epi64(mine_v, shift), e);
+    let mut r = _mm256_and_si256(_mm256_srlv_epi64(mine_v, shift), e);
+    for _ in 0..5 {
+        l = _mm256_or_si256(l, _mm256_and_si256(_mm256_sllv_epi64(l, shift), e));
+        r = _mm256_or_si256(r, _mm256_and_si256(_mm256_srlv_epi64(r, shift), e));
+    }
+    let moves = _mm256_or_si256(_mm256_sllv_epi64(l, shift), _mm256_srlv_epi64(r, shift));
+
+    or_lanes(moves) & !(mine | others)
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn flipped_set_avx2(mine: u64, others: u64, pos: u64) -> u64 {
+    let (shift, mask) = shifts_and_masks();
+    let pos_v = _mm256_set1_epi64x(pos as i64);
+    let mine_v = _mm256_set1_epi64x(mine as i64);
+    let e = _mm256_and_si256(_mm256_set1_epi64x(others as i64), mask);
+
+    let mut pos_l = _mm256_and_si256(_mm256_sllv_epi64(pos_v, shift), e);
+    let mut mine_r = _mm256_and_si256(_mm256_srlv_epi64(mine_v, shift), e);
+    let mut pos_r = _mm256_and_si256(_mm256_srlv_epi64(pos_v, shift), e);
+    let mut mine_l = _mm256_and_si256(_mm256_sllv_epi64(mine_v, shift), e);
+    for _ in 0..5 {
+        pos_l = _mm256_or_si256(pos_l, _mm256_and_si256(_mm256_sllv_epi64(pos_l, shift), e));
+        mine_r = _mm256_or_si256(
+            mine_r,
+            _mm256_and_si256(_mm256_srlv_epi64(mine_r, shift), e),
+        );
+        pos_r = _mm256_or_si256(pos_r, _mm256_and_si256(_mm256_srlv_epi64(pos_r, shift), e));
+        mine_l = _mm256_or_si256(
+            mine_l,
+            _mm256_and_si256(_mm256_sllv_epi64(mine_l, shift), e),
+        );
+    }
+    let flipped = _mm256_or_si256(
+        _mm256_and_si256(pos_l, mine_r),
+        _mm256_and_si256(pos_r, mine_l),
+    );
+
+    or_lanes(flipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_board;
+    use rand::prelude::*;
+
+    #[test]
+    fn same_as_portable() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for plies in 0..1000 {
+            let board = random_board(&mut rng, plies % 60);
+
+            assert_eq!(
+                flip_candidates(&board).unwrap(),
+                board.flip_candidates_portable()
+            );
+            for pos in board.empty_cells() {
+                assert_eq!(
+                    flipped_set_unchecked(&board, pos).unwrap(),
+                    board.flipped_set_unchecked_portable(pos)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use super::{axis::Axis, Board, PosSet};
+
+impl Board {
+    pub fn stable_disks(&self) -> (PosSet, PosSet) {
+        let empty_cells = !(self.mine_disks | self.others_disks);
+        let protected = Axis::ALL.map(|axis| axis.full_lines(empty_cells) | axis.edge());
+
+        let stable = |disks: PosSet| {
+            let mut stable = PosSet::new();
+            loop {
+                let mut next = disks;
+                for (axis, protected) in Axis::ALL.iter().zip(protected) {
+                    next &= protected | axis.neighbors(stable);
+                }
+                if next == stable {
+                    return stable;
+                }
+                stable = next;
+            }
+        };
+
+        (stable(self.mine_disks), stable(self.others_disks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::random_board, Disk, Pos};
+    use rand::prelude::*;
+    use std::str::FromStr;
+
+    fn stable_disks_reference(board: &Board) -> (PosSet, PosSet) {
+        const DIRS: [(i8, i8); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+
+        let is_full_line = |pos: Pos, (dx, dy): (i8, i8)| {
+            [(dx, dy), (-dx, -dy)].into_iter().all(|(dx, dy)| {
+                let (mut x, mut y) = (pos.x() + dx, pos.y() + dy);
+                while let Some(p) = Pos::from_xy(x, y) {
+                    if board.get_disk(p).is_none() {
+                        return false;
+                    }
+                    x += dx;
+                    y += dy;
+                }
+                true
+            })
+        };
+
+        let stable = |disk: Disk| {
+            let mut stable = PosSet::new();
+            loop {
+                let mut next = PosSet::new();
+                for pos in Pos::iter_all() {
+                    if board.get_disk(pos) != Some(disk) {
+                        continue;
+                    }
+                    let is_stable = DIRS.into_iter().all(|(dx, dy)| {
+                        is_full_line(pos, (dx, dy))
+                            || [(dx, dy), (-dx, -dy)].into_iter().any(
+                                |(dx, dy)| match Pos::from_xy(pos.x() + dx, pos.y() + dy) {
+                                    Some(p) => stable.contains(&p),
+                                    None => true,
+                                },
+                            )
+                    });
+                    if is_stable {
+                        next |= pos;
+                    }
+                }
+                if next == stable {
+                    return stable;
+                }
+                stable = next;
+            }
+        };
+
+        (stable(Disk::Mine), stable(Disk::Others))
+    }
+
+    #[test]
+    fn stable_disks() {
+        let board = Board::new();
+        assert_eq!(board.stable_disks(), (PosSet::new(), PosSet::new()));
+
+        #[rustfmt::skip]
+        let board = Board::from_str(concat!(
+            "XXXO---O",
+            "XX-----O",
+            "X-------",
+            "--------",
+            "--------",
+            "--------",
+            "--------",
+            "OXXXXXXX",
+        ))
+        .unwrap();
+        let (mine, others) = board.stable_disks();
+        assert_eq!(
+            mine,
+            PosSet::from_iter([
+                Pos::A1,
+                Pos::B1,
+                Pos::C1,
+                Pos::A2,
+                Pos::B2,
+                Pos::A3,
+                Pos::B8,
+                Pos::C8,
+                Pos::D8,
+                Pos::E8,
+                Pos::F8,
+                Pos::G8,
+                Pos::H8,
+            ])
+        );
+        assert_eq!(others, PosSet::from_iter([Pos::H1, Pos::H2, Pos::A8]));
+    }
+
+    #[test]
+    fn compare_with_reference() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for plies in 0..1000 {
+            let board = random_board(&mut rng, plies % 64);
+            assert_eq!(board.stable_disks(), stable_disks_reference(&board));
+        }
+    }
+
+    #[test]
+    fn never_flipped() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for plies in 20..100 {
+            let board = random_board(&mut rng, plies % 64);
+            let (mine, others) = board.stable_disks();
+            for _ in 0..10 {
+                let mut board = board;
+                let (mut mine, mut others) = (mine, others);
+                loop {
+                    let (current_mine, current_others) = (board.mine_disks, board.others_disks);
+                    assert_eq!(current_mine & mine, mine);
+                    assert_eq!(current_others & others, others);
+                    match board.all_flipped().choose(&mut rng) {
+                        Some((_, flipped)) => board = flipped,
+                        None if board.reverse().can_play() => board = board.reverse(),
+                        None => break,
+                    }
+                    std::mem::swap(&mut mine, &mut others);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transform {
+    Identity,
+    // (x, y) -> (7 - y, x)
+    Rotate90,
+    // (x, y) -> (7 - x, 7 - y)
+    Rotate180,
+    // (x, y) -> (y, 7 - x)
+    Rotate270,
+    // (x, y) -> (7 - x, y)
+    FlipHorizontal,
+    // (x, y) -> (x, 7 - y)
+    FlipVertical,
+    // (x, y) -> (y, x)
+    FlipDiagonal,
+    // (x, y) -> (7 - y, 7 - x)
+    FlipAntiDiagonal,
+}
+
+impl Transform {
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::FlipHorizontal,
+        Self::FlipVertical,
+        Self::FlipDiagonal,
+        Self::FlipAntiDiagonal,
+    ];
+
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::Rotate90 => Self::Rotate270,
+            Self::Rotate270 => Self::Rotate90,
+            _ => *self,
+        }
+    }
+
+    pub(super) fn apply(&self, bits: u64) -> u64 {
+        match self {
+            Self::Identity => bits,
+            Self::Rotate90 => flip_horizontal(flip_diagonal(bits)),
+            Self::Rotate180 => bits.reverse_bits(),
+            Self::Rotate270 => flip_vertical(flip_diagonal(bits)),
+            Self::FlipHorizontal => flip_horizontal(bits),
+            Self::FlipVertical => flip_vertical(bits),
+            Self::FlipDiagonal => flip_diagonal(bits),
+            Self::FlipAntiDiagonal => flip_anti_diagonal(bits),
+        }
+    }
+}
+
+fn flip_horizontal(mut bits: u64) -> u64 {
+    const K1: u64 = 0x5555_5555_5555_5555;
+    const K2: u64 = 0x3333_3333_3333_3333;
+    const K4: u64 = 0x0f0f_0f0f_0f0f_0f0f;
+    bits = ((bits >> 1) & K1) | ((bits & K1) << 1);
+    bits = ((bits >> 2) & K2) | ((bits & K2) << 2);
+    ((bits >> 4) & K4) | ((bits & K4) << 4)
+}
+
+fn flip_vertical(bits: u64) -> u64 {
+    bits.swap_bytes()
+}
+
+fn flip_diagonal(mut bits: u64) -> u64 {
+    const K1: u64 = 0x5500_5500_5500_5500;
+    const K2: u64 = 0x3333_0000_3333_0000;
+    const K4: u64 = 0x0f0f_0f0f_0000_0000;
+    let t = K4 & (bits ^ (bits << 28));
+    bits ^= t ^ (t >> 28);
+    let t = K2 & (bits ^ (bits << 14));
+    bits ^= t ^ (t >> 14);
+    let t = K1 & (bits ^ (bits << 7));
+    bits ^ t ^ (t >> 7)
+}
+
+fn flip_anti_diagonal(mut bits: u64) -> u64 {
+    const K1: u64 = 0xaa00_aa00_aa00_aa00;
+    const K2: u64 = 0xcccc_0000_cccc_0000;
+    const K4: u64 = 0xf0f0_f0f0_0f0f_0f0f;
+    let t = bits ^ (bits << 36);
+    bits ^= K4 & (t ^ (bits >> 36));
+    let t = K2 & (bits ^ (bits << 18));
+    bits ^= t ^ (t >> 18);
+    let t = K1 & (bits ^ (bits << 9));
+    bits ^ t ^ (t >> 9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Board, Pos};
+
+    #[test]
+    fn transform_pos() {
+        const MAX: i8 = Board::SIZE - 1;
+        for pos in Pos::iter_all() {
+            let (x, y) = (pos.x(), pos.y());
+            for (transform, expected) in [
+                (Transform::Identity, (x, y)),
+                (Transform::Rotate90, (MAX - y, x)),
+                (Transform::Rotate180, (MAX - x, MAX - y)),
+                (Transform::Rotate270, (y, MAX - x)),
+                (Transform::FlipHorizontal, (MAX - x, y)),
+                (Transform::FlipVertical, (x, MAX - y)),
+                (Transform::FlipDiagonal, (y, x)),
+                (Transform::FlipAntiDiagonal, (MAX - y, MAX - x)),
+            ] {
+                let transformed = pos.transform(transform);
+                assert_eq!((transformed.x(), transformed.y()), expected);
+                assert_eq!(transformed.transform(transform.inverse()), pos);
+            }
+        }
+    }
+}
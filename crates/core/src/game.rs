@@ -1,12 +1,54 @@
-use crate::{Board, Color, Disk, Pos, PosIter};
-use std::iter::FusedIterator;
+pub use self::{observer::*, result::*, transcript::*};
+use crate::{Board, Color, Pos, PosIter, Position};
+use std::{fmt, iter::FusedIterator};
+
+mod observer;
+mod result;
+mod transcript;
 
 #[derive(Debug, Clone)]
 pub struct Game {
+    start: Position,
     state: GameState,
-    board: Board,
-    turn_color: Color,
-    history: Vec<Board>,
+    position: Position,
+    moves: Vec<MoveRecord>,
+    history: Vec<HistoryEntry>,
+    redo_moves: Vec<Pos>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HistoryEntry {
+    position: Position,
+    moves_len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Move {
+    Put(Pos),
+    Pass,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Move::Put(pos) => pos.fmt(f),
+            Move::Pass => f.write_str("Pass"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveRecord {
+    pub color: Color,
+    pub mv: Move,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PutOutcome {
+    Turn,
+    Pass,
+    GameOver,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -18,10 +60,12 @@ enum GameState {
 impl Default for Game {
     fn default() -> Self {
         Self {
+            start: Position::default(),
             state: GameState::Turn,
-            board: Board::default(),
-            turn_color: Color::Black,
+            position: Position::default(),
+            moves: vec![],
             history: vec![],
+            redo_moves: vec![],
         }
     }
 }
@@ -34,16 +78,84 @@ pub enum PutError {
     CannotPut(Pos),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum StartPositionError {
+    #[error("center square {0} is empty")]
+    EmptyCenter(Pos),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    #[error("invalid start position: {0}")]
+    StartPosition(#[from] StartPositionError),
+    #[error("illegal move at index {index}: {source}")]
+    IllegalMove {
+        index: usize,
+        #[source]
+        source: PutError,
+    },
+    #[error("unexpected pass at index {index}")]
+    UnexpectedPass { index: usize },
+    #[error("missing pass at index {index}")]
+    MissingPass { index: usize },
+}
+
 impl Game {
     pub fn new() -> Self {
         Self::default()
     }
 
     pub fn with_board(board: Board) -> Self {
+        let position = Position::new(board, Color::Black);
         Self {
-            board,
+            start: position,
+            position,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_position(board: Board, color: Color) -> Result<Self, StartPositionError> {
+        for pos in [Pos::D4, Pos::E4, Pos::D5, Pos::E5] {
+            if board.get_disk(pos).is_none() {
+                return Err(StartPositionError::EmptyCenter(pos));
+            }
+        }
+
+        let position = Position::new(board, color);
+        let mut game = Self {
+            start: position,
+            position,
             ..Default::default()
+        };
+        game.update_state();
+        Ok(game)
+    }
+
+    pub fn replay(start: Position, moves: &[Move]) -> Result<Self, ReplayError> {
+        let mut game = Self::with_position(*start.board(), start.to_move())?;
+        for (index, mv) in moves.iter().enumerate() {
+            if game.moves.len() > index {
+                if *mv != Move::Pass {
+                    return Err(ReplayError::MissingPass { index });
+                }
+                continue;
+            }
+            match *mv {
+                Move::Put(pos) => {
+                    game.put_disk(pos)
+                        .map_err(|source| ReplayError::IllegalMove { index, source })?;
+                }
+                Move::Pass => return Err(ReplayError::UnexpectedPass { index }),
+            }
+        }
+        if game.moves.len() > moves.len() {
+            return Err(ReplayError::MissingPass { index: moves.len() });
         }
+        Ok(game)
+    }
+
+    pub fn start_position(&self) -> &Position {
+        &self.start
     }
 
     fn is_game_over(&self) -> bool {
@@ -53,39 +165,31 @@ impl Game {
         }
     }
 
+    pub fn position(&self) -> &Position {
+        &self.position
+    }
+
     pub fn board(&self) -> &Board {
-        &self.board
+        self.position.board()
     }
 
     pub fn turn(&self) -> u32 {
-        self.board.count_all_disks()
+        self.board().count_all_disks()
     }
 
     pub fn turn_color(&self) -> Option<Color> {
         if self.is_game_over() {
             return None;
         }
-        Some(self.turn_color)
-    }
-
-    fn disk2color(&self, disk: Disk) -> Color {
-        match disk {
-            Disk::Mine => self.turn_color,
-            Disk::Others => self.turn_color.reverse(),
-        }
+        Some(self.position.to_move())
     }
 
-    fn color2disk(&self, color: Color) -> Disk {
-        if color == self.turn_color {
-            Disk::Mine
-        } else {
-            Disk::Others
-        }
+    pub fn count_disk(&self, color: Option<Color>) -> u32 {
+        self.position.count_disk(color)
     }
 
-    pub fn count_disk(&self, color: Option<Color>) -> u32 {
-        let disk = color.map(|color| self.color2disk(color));
-        self.board.count_disk(disk)
+    pub fn moves(&self) -> &[MoveRecord] {
+        &self.moves
     }
 
     pub fn disks(&self) -> Disks {
@@ -97,31 +201,87 @@ impl Game {
     }
 
     pub fn get_disk(&self, pos: Pos) -> Option<Color> {
-        self.board.get_disk(pos).map(|disk| self.disk2color(disk))
+        self.position.get_disk(pos)
+    }
+
+    pub fn put_disk(&mut self, pos: Pos) -> Result<PutOutcome, PutError> {
+        let outcome = self.put_disk_inner(pos)?;
+        self.redo_moves.clear();
+        Ok(outcome)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_moves.is_empty()
+    }
+
+    pub fn undo(&mut self) -> Option<Pos> {
+        let HistoryEntry {
+            position,
+            moves_len,
+        } = self.history.pop()?;
+        let pos = match self.moves[moves_len].mv {
+            Move::Put(pos) => pos,
+            Move::Pass => unreachable!(),
+        };
+        self.state = GameState::Turn;
+        self.position = position;
+        self.moves.truncate(moves_len);
+        self.redo_moves.push(pos);
+        Some(pos)
+    }
+
+    pub fn redo(&mut self) -> Option<Pos> {
+        self.redo_inner().map(|(pos, _outcome)| pos)
     }
 
-    pub fn put_disk(&mut self, pos: Pos) -> Result<(), PutError> {
+    fn redo_inner(&mut self) -> Option<(Pos, PutOutcome)> {
+        let pos = self.redo_moves.pop()?;
+        let outcome = self
+            .put_disk_inner(pos)
+            .expect("redo move must be valid in the undone position");
+        Some((pos, outcome))
+    }
+
+    fn put_disk_inner(&mut self, pos: Pos) -> Result<PutOutcome, PutError> {
         if self.is_game_over() {
             return Err(PutError::GameOver);
         }
 
-        let flipped = self.board.flipped(pos).ok_or(PutError::CannotPut(pos))?;
+        let next = self.position.play(pos).ok_or(PutError::CannotPut(pos))?;
 
-        self.history.push(self.board);
+        self.history.push(HistoryEntry {
+            position: self.position,
+            moves_len: self.moves.len(),
+        });
+        self.moves.push(MoveRecord {
+            color: self.position.to_move(),
+            mv: Move::Put(pos),
+        });
+        self.position = next;
 
-        self.board = flipped;
-        self.turn_color = self.turn_color.reverse();
+        Ok(self.update_state())
+    }
 
-        for _ in 0..2 {
-            if self.board.can_play() {
-                return Ok(());
-            }
-            self.board = self.board.reverse();
-            self.turn_color = self.turn_color.reverse();
+    fn update_state(&mut self) -> PutOutcome {
+        if self.position.can_play() {
+            return PutOutcome::Turn;
+        }
+        let passed = self.position.pass();
+        if passed.can_play() {
+            self.moves.push(MoveRecord {
+                color: self.position.to_move(),
+                mv: Move::Pass,
+            });
+            self.position = passed;
+            return PutOutcome::Pass;
         }
 
         self.state = GameState::GameOver;
-        Ok(())
+        PutOutcome::GameOver
     }
 }
 
@@ -198,3 +358,173 @@ impl DoubleEndedIterator for PosDisks<'_> {
 
 impl ExactSizeIterator for PosDisks<'_> {}
 impl FusedIterator for PosDisks<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::PASS_MOVES;
+
+    #[test]
+    fn undo_redo() {
+        use Pos as P;
+
+        let mut game = Game::new();
+        assert!(!game.can_undo());
+        assert!(!game.can_redo());
+        assert_eq!(game.undo(), None);
+        assert_eq!(game.redo(), None);
+
+        game.put_disk(P::F5).unwrap();
+        game.put_disk(P::D6).unwrap();
+        let board = *game.board();
+        assert_eq!(game.turn_color(), Some(Color::Black));
+
+        assert_eq!(game.undo(), Some(P::D6));
+        assert_eq!(game.turn_color(), Some(Color::White));
+        assert_eq!(game.undo(), Some(P::F5));
+        assert_eq!(game.turn_color(), Some(Color::Black));
+        assert_eq!(*game.board(), Board::new());
+        assert!(!game.can_undo());
+        assert!(game.can_redo());
+
+        assert_eq!(game.redo(), Some(P::F5));
+        assert_eq!(game.redo(), Some(P::D6));
+        assert_eq!(*game.board(), board);
+        assert_eq!(game.turn_color(), Some(Color::Black));
+        assert!(!game.can_redo());
+
+        game.undo();
+        game.put_disk(P::F4).unwrap();
+        assert!(!game.can_redo());
+        assert_eq!(
+            game.moves(),
+            [
+                MoveRecord {
+                    color: Color::Black,
+                    mv: Move::Put(P::F5)
+                },
+                MoveRecord {
+                    color: Color::White,
+                    mv: Move::Put(P::F4)
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn undo_pass_and_game_over() {
+        use Pos as P;
+
+        // shortest game: black wipes out white in 9 moves
+        let hands = [
+            P::D3,
+            P::C3,
+            P::B3,
+            P::D2,
+            P::E1,
+            P::D6,
+            P::D7,
+            P::E3,
+            P::F4,
+        ];
+        let mut game = Game::new();
+        let mut boards = vec![];
+        for (i, hand) in hands.into_iter().enumerate() {
+            boards.push((*game.board(), game.turn_color()));
+            let expected = if i + 1 < hands.len() {
+                PutOutcome::Turn
+            } else {
+                PutOutcome::GameOver
+            };
+            assert_eq!(game.put_disk(hand).unwrap(), expected);
+        }
+        assert_eq!(game.turn_color(), None);
+        assert_eq!(game.moves().len(), hands.len());
+        assert_eq!(game.count_disk(Some(Color::White)), 0);
+
+        for (hand, (board, turn_color)) in hands.into_iter().zip(boards).rev() {
+            assert_eq!(game.undo(), Some(hand));
+            assert_eq!(*game.board(), board);
+            assert_eq!(game.turn_color(), turn_color);
+        }
+        assert!(!game.can_undo());
+
+        while game.redo().is_some() {}
+        assert_eq!(game.turn_color(), None);
+
+        let mut game = Game::new();
+        let (&last, hands) = PASS_MOVES.split_last().unwrap();
+        for &hand in hands {
+            game.put_disk(hand).unwrap();
+        }
+        assert_eq!(game.turn_color(), Some(Color::White));
+        assert_eq!(game.put_disk(last).unwrap(), PutOutcome::Pass);
+        // black cannot play, so white moves again
+        assert_eq!(game.turn_color(), Some(Color::White));
+        assert_eq!(
+            game.moves()[game.moves().len() - 2..],
+            [
+                MoveRecord {
+                    color: Color::White,
+                    mv: Move::Put(P::C1)
+                },
+                MoveRecord {
+                    color: Color::Black,
+                    mv: Move::Pass
+                }
+            ]
+        );
+        assert_eq!(game.undo(), Some(P::C1));
+        assert_eq!(game.turn_color(), Some(Color::White));
+        assert_eq!(game.moves().len(), 7);
+        assert_eq!(game.redo(), Some(P::C1));
+        assert_eq!(game.turn_color(), Some(Color::White));
+        assert_eq!(game.moves().len(), 9);
+    }
+
+    #[test]
+    fn with_position() {
+        use std::str::FromStr;
+
+        let position = Position::from_str(
+            "---------------------------OX------XXX-------------------------- O",
+        )
+        .unwrap();
+        let game = Game::with_position(*position.board(), position.to_move()).unwrap();
+        assert_eq!(game.turn_color(), Some(Color::White));
+        assert_eq!(*game.start_position(), position);
+        assert!(game.moves().is_empty());
+
+        // white cannot flip the corner disk, so white passes and black moves
+        let board =
+            Board::from_str("O--------X-----------------XX------XX---------------------------")
+                .unwrap();
+        let mut game = Game::with_position(board, Color::White).unwrap();
+        assert_eq!(game.turn_color(), Some(Color::Black));
+        assert_eq!(
+            game.moves(),
+            [MoveRecord {
+                color: Color::White,
+                mv: Move::Pass
+            }]
+        );
+        assert_eq!(game.get_disk(Pos::A1), Some(Color::Black));
+        assert_eq!(game.count_disk(Some(Color::White)), 5);
+        game.put_disk(Pos::C3).unwrap();
+        assert_eq!(game.undo(), Some(Pos::C3));
+        assert!(!game.can_undo());
+        assert_eq!(game.moves().len(), 1);
+
+        // neither side can play
+        let board =
+            Board::from_str("---------------------------XX------XX---------------------------")
+                .unwrap();
+        let game = Game::with_position(board, Color::Black).unwrap();
+        assert_eq!(game.turn_color(), None);
+
+        assert!(matches!(
+            Game::with_position(Board::empty(), Color::Black),
+            Err(StartPositionError::EmptyCenter(Pos::D4))
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+use super::{Game, GameResult, PutError, PutOutcome};
+use crate::{Color, Pos, PosSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEvent {
+    MovePlayed {
+        color: Color,
+        pos: Pos,
+        flipped: PosSet,
+    },
+    Pass {
+        color: Color,
+    },
+    GameOver {
+        result: GameResult,
+    },
+    Undo {
+        color: Color,
+        pos: Pos,
+        flipped: PosSet,
+    },
+}
+
+pub trait GameObserver {
+    fn on_event(&mut self, game: &Game, event: &GameEvent);
+}
+
+impl<F> GameObserver for F
+where
+    F: FnMut(&Game, &GameEvent),
+{
+    fn on_event(&mut self, game: &Game, event: &GameEvent) {
+        self(game, event)
+    }
+}
+
+impl GameObserver for Vec<GameEvent> {
+    fn on_event(&mut self, _game: &Game, event: &GameEvent) {
+        self.push(*event);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ObservedGame<O> {
+    game: Game,
+    observer: O,
+}
+
+impl<O> ObservedGame<O>
+where
+    O: GameObserver,
+{
+    pub fn new(game: Game, observer: O) -> Self {
+        Self { game, observer }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    pub fn into_inner(self) -> (Game, O) {
+        (self.game, self.observer)
+    }
+
+    pub fn put_disk(&mut self, pos: Pos) -> Result<PutOutcome, PutError> {
+        let color = self.game.turn_color();
+        let flipped = self.game.board().flipped_set(pos);
+        let outcome = self.game.put_disk(pos)?;
+        self.emit_put(color, pos, flipped, outcome);
+        Ok(outcome)
+    }
+
+    pub fn undo(&mut self) -> Option<Pos> {
+        let pos = self.game.undo()?;
+        let color = self
+            .game
+            .turn_color()
+            .expect("undone position must be playable");
+        let flipped = self
+            .game
+            .board()
+            .flipped_set(pos)
+            .expect("undone move must be legal");
+        self.emit(GameEvent::Undo {
+            color,
+            pos,
+            flipped,
+        });
+        Some(pos)
+    }
+
+    pub fn redo(&mut self) -> Option<Pos> {
+        let pos = *self.game.redo_moves.last()?;
+        let color = self.game.turn_color();
+        let flipped = self.game.board().flipped_set(pos);
+        let (pos, outcome) = self.game.redo_inner()?;
+        self.emit_put(color, pos, flipped, outcome);
+        Some(pos)
+    }
+
+    fn emit_put(
+        &mut self,
+        color: Option<Color>,
+        pos: Pos,
+        flipped: Option<PosSet>,
+        outcome: PutOutcome,
+    ) {
+        // `put_disk` has succeeded, so there was a side to move and disks were flipped
+        let color = color.expect("side to move must exist");
+        let flipped = flipped.expect("legal move must flip disks");
+        self.emit(GameEvent::MovePlayed {
+            color,
+            pos,
+            flipped,
+        });
+        match outcome {
+            PutOutcome::Turn => {}
+            PutOutcome::Pass => self.emit(GameEvent::Pass {
+                color: color.reverse(),
+            }),
+            PutOutcome::GameOver => {
+                let result = self.game.result().expect("game must be over");
+                self.emit(GameEvent::GameOver { result });
+            }
+        }
+    }
+
+    fn emit(&mut self, event: GameEvent) {
+        self.observer.on_event(&self.game, &event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::PASS_MOVES;
+
+    #[test]
+    fn events() {
+        let mut game = ObservedGame::new(Game::new(), vec![]);
+        let log = |game: &mut ObservedGame<Vec<GameEvent>>| {
+            game.observer_mut().drain(..).collect::<Vec<_>>()
+        };
+
+        assert!(game.put_disk(Pos::A1).is_err());
+        assert_eq!(log(&mut game), []);
+
+        game.put_disk(PASS_MOVES[0]).unwrap();
+        assert_eq!(
+            log(&mut game),
+            [GameEvent::MovePlayed {
+                color: Color::Black,
+                pos: Pos::D3,
+                flipped: PosSet::new() | Pos::D4,
+            }]
+        );
+
+        for &pos in &PASS_MOVES[1..7] {
+            game.put_disk(pos).unwrap();
+        }
+        assert_eq!(log(&mut game).len(), 6);
+
+        game.put_disk(PASS_MOVES[7]).unwrap();
+        let events = log(&mut game);
+        assert!(matches!(
+            events[..],
+            [
+                GameEvent::MovePlayed {
+                    color: Color::White,
+                    pos: Pos::C1,
+                    ..
+                },
+                GameEvent::Pass {
+                    color: Color::Black
+                }
+            ]
+        ));
+
+        assert_eq!(game.undo(), Some(Pos::C1));
+        let flipped = match events[0] {
+            GameEvent::MovePlayed { flipped, .. } => flipped,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            log(&mut game),
+            [GameEvent::Undo {
+                color: Color::White,
+                pos: Pos::C1,
+                flipped,
+            }]
+        );
+
+        assert_eq!(game.redo(), Some(Pos::C1));
+        assert_eq!(log(&mut game), events);
+    }
+
+    #[test]
+    fn game_over() {
+        let mut events = vec![];
+        let mut game = ObservedGame::new(Game::new(), |_: &Game, event: &GameEvent| {
+            events.push(*event)
+        });
+        for pos in [
+            Pos::D3,
+            Pos::C3,
+            Pos::B3,
+            Pos::D2,
+            Pos::E1,
+            Pos::D6,
+            Pos::D7,
+            Pos::E3,
+            Pos::F4,
+        ] {
+            game.put_disk(pos).unwrap();
+        }
+        let result = game.game().result().unwrap();
+        drop(game);
+        assert_eq!(events.last(), Some(&GameEvent::GameOver { result }));
+        assert_eq!(events.len(), 10);
+    }
+}
@@ -0,0 +1,130 @@
+use super::Game;
+use crate::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EmptySquares {
+    #[default]
+    ToWinner,
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GameResult {
+    black: u32,
+    white: u32,
+    empty: u32,
+    empty_squares: EmptySquares,
+}
+
+impl GameResult {
+    /// Returns `None` if there are more than 64 disks.
+    pub fn new(black: u32, white: u32, empty_squares: EmptySquares) -> Option<Self> {
+        let empty = 64u32.checked_sub(black)?.checked_sub(white)?;
+        Some(Self {
+            black,
+            white,
+            empty,
+            empty_squares,
+        })
+    }
+
+    pub fn winner(&self) -> Option<Color> {
+        match self.black.cmp(&self.white) {
+            std::cmp::Ordering::Less => Some(Color::White),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Greater => Some(Color::Black),
+        }
+    }
+
+    pub fn count_disk(&self, color: Option<Color>) -> u32 {
+        match color {
+            Some(Color::Black) => self.black,
+            Some(Color::White) => self.white,
+            None => self.empty,
+        }
+    }
+
+    pub fn score(&self, color: Color) -> u32 {
+        let disks = self.count_disk(Some(color));
+        match self.empty_squares {
+            EmptySquares::Ignore => disks,
+            EmptySquares::ToWinner => match self.winner() {
+                Some(winner) if winner == color => disks + self.empty,
+                Some(_) => disks,
+                None => disks + self.empty / 2,
+            },
+        }
+    }
+
+    pub fn disc_diff(&self) -> i32 {
+        self.score(Color::Black) as i32 - self.score(Color::White) as i32
+    }
+}
+
+impl Game {
+    pub fn result(&self) -> Option<GameResult> {
+        self.result_with(EmptySquares::default())
+    }
+
+    pub fn result_with(&self, empty_squares: EmptySquares) -> Option<GameResult> {
+        if self.turn_color().is_some() {
+            return None;
+        }
+        GameResult::new(
+            self.count_disk(Some(Color::Black)),
+            self.count_disk(Some(Color::White)),
+            empty_squares,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Board;
+    use std::str::FromStr;
+
+    #[test]
+    fn empty_squares() {
+        assert!(Game::new().result().is_none());
+
+        // black center disks against an unflippable white corner
+        let board =
+            Board::from_str("O--------------------------XX------XX---------------------------")
+                .unwrap();
+        let game = Game::with_position(board, Color::Black).unwrap();
+        let result = game.result().unwrap();
+        assert_eq!(result.winner(), Some(Color::Black));
+        assert_eq!(result.count_disk(Some(Color::Black)), 4);
+        assert_eq!(result.count_disk(Some(Color::White)), 1);
+        assert_eq!(result.count_disk(None), 59);
+        assert_eq!(result.score(Color::Black), 63);
+        assert_eq!(result.score(Color::White), 1);
+        assert_eq!(result.disc_diff(), 62);
+
+        let result = game.result_with(EmptySquares::Ignore).unwrap();
+        assert_eq!(result.score(Color::Black), 4);
+        assert_eq!(result.disc_diff(), 3);
+
+        let game = Game::with_position(board.reverse(), Color::White).unwrap();
+        assert_eq!(game.result().unwrap().winner(), Some(Color::Black));
+
+        // draw splits the empty squares
+        let board =
+            Board::from_str("O------O-------------------XX------XX------------------O-------O")
+                .unwrap();
+        let result = Game::with_position(board, Color::Black)
+            .unwrap()
+            .result()
+            .unwrap();
+        assert_eq!(result.winner(), None);
+        assert_eq!(result.score(Color::Black), 32);
+        assert_eq!(result.score(Color::White), 32);
+        assert_eq!(result.disc_diff(), 0);
+
+        let result = GameResult::new(40, 24, EmptySquares::default()).unwrap();
+        assert_eq!(result.count_disk(None), 0);
+        assert!(GameResult::new(40, 25, EmptySquares::default()).is_none());
+        assert!(GameResult::new(u32::MAX, 1, EmptySquares::default()).is_none());
+    }
+}
@@ -0,0 +1,117 @@
+use super::{Game, Move, PutError};
+use crate::{ParsePosError, Pos};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseTranscriptError {
+    #[error("incomplete move at index {index}")]
+    Incomplete { index: usize },
+    #[error("invalid move at index {index}: {source}")]
+    InvalidPos {
+        index: usize,
+        #[source]
+        source: ParsePosError,
+    },
+    #[error("illegal move at index {index}: {source}")]
+    IllegalMove {
+        index: usize,
+        #[source]
+        source: PutError,
+    },
+}
+
+impl Game {
+    pub fn from_transcript(s: &str) -> Result<Self, ParseTranscriptError> {
+        let mut game = Self::new();
+        let mut chars = s.chars().filter(|ch| !ch.is_ascii_whitespace());
+        let mut index = 0;
+        while let Some(alpha) = chars.next() {
+            let num = chars
+                .next()
+                .ok_or(ParseTranscriptError::Incomplete { index })?;
+            let pos = format!("{}{}", alpha, num)
+                .parse::<Pos>()
+                .map_err(|source| ParseTranscriptError::InvalidPos { index, source })?;
+            game.put_disk(pos)
+                .map_err(|source| ParseTranscriptError::IllegalMove { index, source })?;
+            index += 1;
+        }
+        Ok(game)
+    }
+
+    pub fn to_transcript(&self) -> String {
+        self.moves()
+            .iter()
+            .filter_map(|record| match record.mv {
+                Move::Put(pos) => Some(pos.to_string().to_ascii_lowercase()),
+                Move::Pass => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::FULL_GAME, Color};
+
+    #[test]
+    fn round_trip() {
+        let game = Game::from_transcript(FULL_GAME).unwrap();
+        assert_eq!(game.turn_color(), None);
+        assert_eq!(game.count_disk(Some(Color::Black)), 19);
+        assert_eq!(game.count_disk(Some(Color::White)), 45);
+        assert_eq!(
+            game.moves()
+                .iter()
+                .filter(|record| record.mv == Move::Pass)
+                .count(),
+            4
+        );
+        assert_eq!(game.to_transcript(), FULL_GAME);
+
+        assert_eq!(Game::from_transcript("").unwrap().to_transcript(), "");
+    }
+
+    #[test]
+    fn case_and_whitespace() {
+        let game = Game::from_transcript("F5d6 C3 d3\nc4").unwrap();
+        assert_eq!(game.to_transcript(), "f5d6c3d3c4");
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            Game::from_transcript("f5d6c"),
+            Err(ParseTranscriptError::Incomplete { index: 2 })
+        ));
+        assert!(matches!(
+            Game::from_transcript("f5d6z3"),
+            Err(ParseTranscriptError::InvalidPos { index: 2, .. })
+        ));
+        assert!(matches!(
+            Game::from_transcript("f5d6i3"),
+            Err(ParseTranscriptError::InvalidPos { index: 2, .. })
+        ));
+        assert!(matches!(
+            Game::from_transcript("f5d6Ā3"),
+            Err(ParseTranscriptError::InvalidPos {
+                index: 2,
+                source: ParsePosError::InvalidAlphabet('Ā')
+            })
+        ));
+        assert!(matches!(
+            Game::from_transcript("f5d6f5"),
+            Err(ParseTranscriptError::IllegalMove {
+                index: 2,
+                source: PutError::CannotPut(Pos::F5)
+            })
+        ));
+        assert!(matches!(
+            Game::from_transcript(&format!("{}a1", FULL_GAME)),
+            Err(ParseTranscriptError::IllegalMove {
+                index: 60,
+                source: PutError::GameOver
+            })
+        ));
+    }
+}
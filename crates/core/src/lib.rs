@@ -1,5 +1,15 @@
-pub use self::{board::*, game::*};
+pub use self::{board::*, game::*, obf::*, opening::*, perft::*, position::*, record::*, save::*};
 
 mod board;
 mod game;
+mod obf;
+mod opening;
+mod perft;
+mod position;
+mod record;
+mod save;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(test)]
+mod test_util;
 mod traits;
@@ -0,0 +1,156 @@
+use crate::{Move, ParsePosError, ParsePositionError, Pos, Position};
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Obf {
+    pub position: Position,
+    pub annotations: Vec<ObfAnnotation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObfAnnotation {
+    pub mv: Move,
+    pub score: Option<i32>,
+}
+
+impl fmt::Display for Obf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{};", self.position)?;
+        for annotation in &self.annotations {
+            write!(f, " {};", annotation)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ObfAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mv {
+            Move::Put(pos) => write!(f, "{}", pos)?,
+            Move::Pass => write!(f, "PS")?,
+        }
+        if let Some(score) = self.score {
+            write!(f, ":{:+}", score)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseObfError {
+    #[error("cannot parse position: {0}")]
+    Position(#[from] ParsePositionError),
+    #[error("cannot parse move `{0}`: {1}")]
+    Move(String, ParsePosError),
+    #[error("cannot parse score `{0}`: {1}")]
+    Score(String, ParseIntError),
+}
+
+impl FromStr for Obf {
+    type Err = ParseObfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split(';').map(str::trim);
+        let position = fields.next().unwrap_or_default().parse()?;
+        let annotations = fields
+            .filter(|field| !field.is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            position,
+            annotations,
+        })
+    }
+}
+
+impl FromStr for ObfAnnotation {
+    type Err = ParseObfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mv, score) = match s.split_once(':') {
+            Some((mv, score)) => (mv.trim(), Some(score.trim())),
+            None => (s, None),
+        };
+        let mv = match mv.to_ascii_uppercase().as_str() {
+            "PS" | "PA" => Move::Pass,
+            _ => Move::Put(
+                mv.parse::<Pos>()
+                    .map_err(|e| ParseObfError::Move(mv.into(), e))?,
+            ),
+        };
+        let score = score
+            .map(|score| {
+                score
+                    .parse()
+                    .map_err(|e| ParseObfError::Score(score.into(), e))
+            })
+            .transpose()?;
+        Ok(Self { mv, score })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_from_str() {
+        let s = "--XXXXX--OOOXX-O-OOOXXOX-OXOXOXXOXXXOXXX--XOXOXX-XXXOOO--OOOOO-- X; G8:+18; H1:+12; H7:+6; A2:+0; A6:-2;";
+        let obf = Obf::from_str(s).unwrap();
+        assert_eq!(obf.annotations.len(), 5);
+        assert_eq!(
+            obf.annotations[0],
+            ObfAnnotation {
+                mv: Move::Put(Pos::G8),
+                score: Some(18)
+            }
+        );
+        assert_eq!(obf.annotations[4].score, Some(-2));
+        assert_eq!(obf.to_string(), s);
+
+        let obf = Obf::from_str(
+            "---------------------------OX------XO--------------------------- X; ps; f5",
+        )
+        .unwrap();
+        assert_eq!(obf.position, Position::default());
+        assert_eq!(
+            obf.annotations,
+            [
+                ObfAnnotation {
+                    mv: Move::Pass,
+                    score: None
+                },
+                ObfAnnotation {
+                    mv: Move::Put(Pos::F5),
+                    score: None
+                }
+            ]
+        );
+        assert_eq!(
+            obf.to_string(),
+            "---------------------------OX------XO--------------------------- X; PS; F5;"
+        );
+
+        let obf =
+            Obf::from_str("---------------------------OX------XO--------------------------- O")
+                .unwrap();
+        assert!(obf.annotations.is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let position = "---------------------------OX------XO--------------------------- X";
+        assert!(matches!(
+            Obf::from_str("X;"),
+            Err(ParseObfError::Position(_))
+        ));
+        assert!(matches!(
+            Obf::from_str(&format!("{}; Z9:+2;", position)),
+            Err(ParseObfError::Move(..))
+        ));
+        assert!(matches!(
+            Obf::from_str(&format!("{}; F5:+a;", position)),
+            Err(ParseObfError::Score(..))
+        ));
+    }
+}
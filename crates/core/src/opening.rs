@@ -0,0 +1,193 @@
+use crate::{Game, Move, Pos, Position};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Opening {
+    name: &'static str,
+    moves: &'static str,
+}
+
+impl Opening {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Pos> {
+        self.moves
+            .as_bytes()
+            .chunks(2)
+            .map(|mv| std::str::from_utf8(mv).unwrap().parse().unwrap())
+    }
+}
+
+// all lines start with f5; the other first moves are reached through symmetry. Names and
+// lines follow Robert Gatliff's list of opening names, and a line is matched only when it
+// has been played in full
+const OPENINGS: &[Opening] = &[
+    Opening {
+        name: "Diagonal Opening",
+        moves: "f5f6",
+    },
+    Opening {
+        name: "Perpendicular Opening",
+        moves: "f5d6",
+    },
+    Opening {
+        name: "Parallel Opening",
+        moves: "f5f4",
+    },
+    Opening {
+        name: "Tiger",
+        moves: "f5d6c3d3c4",
+    },
+    Opening {
+        name: "Stephenson",
+        moves: "f5d6c3d3c4f4c5b3c2",
+    },
+    Opening {
+        name: "No-Kung",
+        moves: "f5d6c3d3c4f4c5b3c2e6c6b4b5d2e3a6c1b1",
+    },
+    Opening {
+        name: "Brightwell",
+        moves: "f5d6c3d3c4f4f6f3e6e7",
+    },
+    Opening {
+        name: "Rose",
+        moves: "f5d6c3d3c4f4f6f3e6e7d7g6f8f7g5h6h4g4h3h5h7",
+    },
+    Opening {
+        name: "Rabbit",
+        moves: "f5d6c5f4e3",
+    },
+    Opening {
+        name: "Italian",
+        moves: "f5d6c5f4e3c6d3f6e6d7",
+    },
+    Opening {
+        name: "Buffalo",
+        moves: "f5f6e6f4c3",
+    },
+    Opening {
+        name: "Cow",
+        moves: "f5f6e6f4e3",
+    },
+    Opening {
+        name: "Chimney",
+        moves: "f5f6e6f4e3d6",
+    },
+    Opening {
+        name: "Heath",
+        moves: "f5f6e6f4g5",
+    },
+];
+
+#[derive(Debug, Clone)]
+pub struct OpeningCatalog {
+    positions: HashMap<Position, Opening>,
+}
+
+impl Default for OpeningCatalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpeningCatalog {
+    pub fn new() -> Self {
+        let mut positions = HashMap::new();
+        for opening in OPENINGS {
+            let position = opening
+                .moves()
+                .try_fold(Position::default(), |position, pos| position.play(pos))
+                .expect("opening must be a legal line");
+            positions.insert(canonical(&position), *opening);
+        }
+        Self { positions }
+    }
+
+    pub fn openings(&self) -> &'static [Opening] {
+        OPENINGS
+    }
+
+    pub fn lookup(&self, position: &Position) -> Option<&Opening> {
+        self.positions.get(&canonical(position))
+    }
+
+    pub fn identify(&self, game: &Game) -> Option<&Opening> {
+        if *game.start_position() != Position::default() {
+            return None;
+        }
+        let mut position = Position::default();
+        let mut found = None;
+        for record in game.moves() {
+            position = match record.mv {
+                Move::Put(pos) => position.play(pos)?,
+                Move::Pass => return found,
+            };
+            if let Some(opening) = self.lookup(&position) {
+                found = Some(opening);
+            }
+        }
+        found
+    }
+}
+
+fn canonical(position: &Position) -> Position {
+    Position::new(position.board().canonical().0, position.to_move())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Board, Transform};
+
+    #[test]
+    fn lookup() {
+        let catalog = OpeningCatalog::new();
+        assert_eq!(catalog.positions.len(), OPENINGS.len());
+
+        let name = |transcript: &str| {
+            catalog
+                .identify(&Game::from_transcript(transcript).unwrap())
+                .map(Opening::name)
+        };
+        assert_eq!(name(""), None);
+        assert_eq!(name("f5"), None);
+        assert_eq!(name("f5d6"), Some("Perpendicular Opening"));
+        assert_eq!(name("f5d6c3"), Some("Perpendicular Opening"));
+        assert_eq!(name("f5d6c3d3c4"), Some("Tiger"));
+        assert_eq!(name("f5d6c3d3c4f4c5b3"), Some("Tiger"));
+        assert_eq!(name("f5d6c3d3c4f4c5b3c2"), Some("Stephenson"));
+        assert_eq!(name("f5f6e6f4e3"), Some("Cow"));
+
+        // the Brightwell is a prefix of the Rose, not the Rose itself
+        let rose = "f5d6c3d3c4f4f6f3e6e7d7g6f8f7g5h6h4g4h3h5h7";
+        assert_eq!(name(&rose[..20]), Some("Brightwell"));
+        assert_eq!(name(&rose[..40]), Some("Brightwell"));
+        assert_eq!(name(rose), Some("Rose"));
+        assert_eq!(name("f5d6c3d3c4f4f6f3e6e7c6"), Some("Brightwell"));
+        assert_eq!(name("f5f6e6f4e3d6g5"), Some("Chimney"));
+
+        // the same openings started from the other three first moves
+        let lines = ["f5f6", "f5d6c3d3c4", "f5d6c3d3c4f4c5b3c2e3", "f5f6e6f4e3"];
+        for transform in Transform::ALL {
+            if Board::new().transform(transform) != Board::new() {
+                continue;
+            }
+            for line in lines {
+                let transformed = Opening {
+                    name: "",
+                    moves: line,
+                }
+                .moves()
+                .map(|pos| pos.transform(transform).to_string())
+                .collect::<String>();
+                assert_eq!(name(&transformed), name(line), "{}", transformed);
+            }
+        }
+
+        // transposition of the Cow
+        assert_eq!(name("f5f4e3f6e6"), Some("Cow"));
+    }
+}
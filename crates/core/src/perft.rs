@@ -0,0 +1,50 @@
+use crate::Board;
+
+pub fn perft(board: &Board, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let candidates = board.flip_candidates();
+    if candidates.is_empty() {
+        let reversed = board.reverse();
+        if !reversed.can_play() {
+            return 1;
+        }
+        return perft(&reversed, depth - 1);
+    }
+
+    if depth == 1 {
+        return u64::from(candidates.count());
+    }
+    board
+        .all_flipped()
+        .map(|(_pos, flipped)| perft(&flipped, depth - 1))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INITIAL: &[u64] = &[
+        1, 4, 12, 56, 244, 1396, 8200, 55092, 390216, 3005288, 24571284, 212258800, 1939886636,
+    ];
+
+    #[test]
+    fn initial() {
+        let board = Board::new();
+        for (depth, expected) in INITIAL.iter().copied().enumerate().take(9) {
+            assert_eq!(perft(&board, depth as u32), expected, "depth {}", depth);
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn initial_deep() {
+        let board = Board::new();
+        for (depth, expected) in INITIAL.iter().copied().enumerate().skip(9) {
+            assert_eq!(perft(&board, depth as u32), expected, "depth {}", depth);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{Board, Color, Disk, ParseBoardError, Pos, PosSet};
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position {
+    board: Board,
+    to_move: Color,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self::new(Board::new(), Color::Black)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let board = match self.to_move {
+            Color::Black => self.board,
+            Color::White => self.board.reverse(),
+        };
+        let side = match self.to_move {
+            Color::Black => 'X',
+            Color::White => 'O',
+        };
+        write!(f, "{} {}", board, side)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParsePositionError {
+    #[error("cannot parse board: {0}")]
+    Board(#[from] ParseBoardError),
+    #[error("side to move not found")]
+    MissingSide,
+    #[error("invalid side to move `{0}`")]
+    InvalidSide(String),
+}
+
+impl FromStr for Position {
+    type Err = ParsePositionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_ascii_whitespace();
+        let board = tokens.next().unwrap_or_default().parse::<Board>()?;
+        let side = tokens.next().ok_or(Self::Err::MissingSide)?;
+        if let Some(token) = tokens.next() {
+            return Err(Self::Err::InvalidSide(format!("{} {}", side, token)));
+        }
+        match side {
+            "X" | "x" => Ok(Self::new(board, Color::Black)),
+            "O" | "o" => Ok(Self::new(board.reverse(), Color::White)),
+            _ => Err(Self::Err::InvalidSide(side.into())),
+        }
+    }
+}
+
+impl Position {
+    pub fn new(board: Board, to_move: Color) -> Self {
+        Self { board, to_move }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn to_move(&self) -> Color {
+        self.to_move
+    }
+
+    pub fn disk_color(&self, disk: Disk) -> Color {
+        match disk {
+            Disk::Mine => self.to_move,
+            Disk::Others => self.to_move.reverse(),
+        }
+    }
+
+    pub fn color_disk(&self, color: Color) -> Disk {
+        if color == self.to_move {
+            Disk::Mine
+        } else {
+            Disk::Others
+        }
+    }
+
+    pub fn get_disk(&self, pos: Pos) -> Option<Color> {
+        self.board.get_disk(pos).map(|disk| self.disk_color(disk))
+    }
+
+    pub fn set_disk(&mut self, pos: Pos, color: Option<Color>) {
+        match color {
+            Some(color) => self.board.set_disk(pos, self.color_disk(color)),
+            None => self.board.unset_disk(pos),
+        }
+    }
+
+    pub fn count_disk(&self, color: Option<Color>) -> u32 {
+        let disk = color.map(|color| self.color_disk(color));
+        self.board.count_disk(disk)
+    }
+
+    pub fn legal_moves(&self) -> PosSet {
+        self.board.flip_candidates()
+    }
+
+    pub fn can_play(&self) -> bool {
+        self.board.can_play()
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        !self.board.can_play() && !self.board.reverse().can_play()
+    }
+
+    pub fn play(&self, pos: Pos) -> Option<Self> {
+        let board = self.board.flipped(pos)?;
+        Some(Self::new(board, self.to_move.reverse()))
+    }
+
+    pub fn pass(&self) -> Self {
+        Self::new(self.board.reverse(), self.to_move.reverse())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Disk, Pos};
+
+    #[test]
+    fn display_from_str() {
+        let initial = "---------------------------OX------XO--------------------------- X";
+        assert_eq!(Position::default().to_string(), initial);
+        assert_eq!(Position::from_str(initial).unwrap(), Position::default());
+
+        // white to move after black's f5
+        let s = "---------------------------OX------XXX-------------------------- O";
+        let position = Position::from_str(s).unwrap();
+        assert_eq!(position.to_move(), Color::White);
+        assert_eq!(*position.board(), Board::new().flipped(Pos::F5).unwrap());
+        assert_eq!(position.board().get_disk(Pos::D4), Some(Disk::Mine));
+        assert_eq!(position.to_string(), s);
+
+        assert!(matches!(
+            Position::from_str(&initial[..64]),
+            Err(ParsePositionError::MissingSide)
+        ));
+        assert!(matches!(
+            Position::from_str(&initial.replace(" X", " Y")),
+            Err(ParsePositionError::InvalidSide(_))
+        ));
+        assert!(matches!(
+            Position::from_str(""),
+            Err(ParsePositionError::Board(ParseBoardError::InvalidLength(0)))
+        ));
+    }
+
+    #[test]
+    fn play() {
+        let position = Position::default();
+        assert_eq!(position.get_disk(Pos::D4), Some(Color::White));
+        assert_eq!(position.get_disk(Pos::E4), Some(Color::Black));
+        assert_eq!(position.get_disk(Pos::A1), None);
+        assert_eq!(
+            position.legal_moves().into_iter().collect::<Vec<_>>(),
+            [Pos::D3, Pos::C4, Pos::F5, Pos::E6]
+        );
+        assert!(position.play(Pos::A1).is_none());
+
+        let position = position.play(Pos::F5).unwrap();
+        assert_eq!(position.to_move(), Color::White);
+        assert_eq!(position.get_disk(Pos::E5), Some(Color::Black));
+        assert_eq!(position.count_disk(Some(Color::Black)), 4);
+        assert_eq!(position.count_disk(Some(Color::White)), 1);
+        assert_eq!(position.count_disk(None), 59);
+        assert!(position.can_play());
+        assert!(!position.is_game_over());
+
+        let passed = position.pass();
+        assert_eq!(passed.to_move(), Color::Black);
+        assert_eq!(passed.get_disk(Pos::E5), Some(Color::Black));
+        assert_eq!(passed.pass(), position);
+    }
+}
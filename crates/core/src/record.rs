@@ -0,0 +1,117 @@
+pub use self::{ggf::*, sgf::*, wthor::*};
+use crate::{Color, Game, Move, Position, ReplayError};
+
+mod ggf;
+mod sgf;
+mod wthor;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameRecord {
+    pub event: Option<String>,
+    pub place: Option<String>,
+    pub date: Option<String>,
+    pub black: RecordPlayer,
+    pub white: RecordPlayer,
+    pub time_control: Option<String>,
+    pub start: Position,
+    pub moves: Vec<RecordMove>,
+    pub result: Option<RecordResult>,
+    pub comment: Option<String>,
+    pub properties: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordPlayer {
+    pub name: String,
+    pub rating: Option<f64>,
+    pub time_control: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordMove {
+    pub mv: Move,
+    pub eval: Option<f64>,
+    pub time: Option<f64>,
+    pub comment: Option<String>,
+    pub variations: Vec<Vec<RecordMove>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecordResult {
+    pub winner: Option<Color>,
+    pub disc_diff: Option<f64>,
+    pub reason: ResultReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResultReason {
+    Normal,
+    Resign,
+    Timeout,
+    Agreement,
+}
+
+impl RecordMove {
+    pub fn new(mv: Move) -> Self {
+        Self {
+            mv,
+            eval: None,
+            time: None,
+            comment: None,
+            variations: vec![],
+        }
+    }
+}
+
+impl RecordResult {
+    pub fn from_disc_diff(disc_diff: f64, reason: ResultReason) -> Self {
+        let winner = if disc_diff > 0.0 {
+            Some(Color::Black)
+        } else if disc_diff < 0.0 {
+            Some(Color::White)
+        } else {
+            None
+        };
+        Self {
+            winner,
+            disc_diff: Some(disc_diff),
+            reason,
+        }
+    }
+}
+
+impl GameRecord {
+    pub fn from_game(game: &Game) -> Self {
+        let result = game.result().map(|result| {
+            RecordResult::from_disc_diff(result.disc_diff().into(), ResultReason::Normal)
+        });
+        Self {
+            start: *game.start_position(),
+            moves: game
+                .moves()
+                .iter()
+                .map(|record| RecordMove::new(record.mv))
+                .collect(),
+            result,
+            ..Default::default()
+        }
+    }
+
+    pub fn to_game(&self) -> Result<Game, ReplayError> {
+        let moves = self.moves.iter().map(|mv| mv.mv).collect::<Vec<_>>();
+        Game::replay(self.start, &moves)
+    }
+}
+
+// some archives omit passes, which shows up as the color not to move making a move
+fn push_move(moves: &mut Vec<RecordMove>, to_move: &mut Color, color: Color, mv: RecordMove) {
+    if *to_move != color {
+        moves.push(RecordMove::new(Move::Pass));
+    }
+    moves.push(mv);
+    *to_move = color.reverse();
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(']', "\\]")
+}
@@ -0,0 +1,457 @@
+use super::{escape, push_move, GameRecord, RecordMove, RecordResult, ResultReason};
+use crate::{Board, Color, Move, ParsePosError, Pos, Position};
+use std::{fmt::Write, num::ParseFloatError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseGgfError {
+    #[error("game start `(;` not found")]
+    MissingStart,
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+    #[error("invalid property name at offset {0}")]
+    InvalidProperty(usize),
+    #[error("unsupported game `{0}`")]
+    UnsupportedGame(String),
+    #[error("unsupported board type `{0}`")]
+    UnsupportedBoardType(String),
+    #[error("invalid board `{0}`")]
+    InvalidBoard(String),
+    #[error("invalid move `{0}`: {1}")]
+    InvalidMove(String, ParsePosError),
+    #[error("invalid number `{1}` in {0}: {2}")]
+    InvalidNumber(String, String, ParseFloatError),
+    #[error("invalid result `{0}`")]
+    InvalidResult(String),
+}
+
+impl GameRecord {
+    pub fn from_ggf(s: &str) -> Result<Self, ParseGgfError> {
+        let mut parser = Parser { s, offset: 0 };
+        parser.next_game()?.ok_or(ParseGgfError::MissingStart)
+    }
+
+    pub fn from_ggf_all(s: &str) -> Result<Vec<Self>, ParseGgfError> {
+        let mut parser = Parser { s, offset: 0 };
+        let mut records = vec![];
+        while let Some(record) = parser.next_game()? {
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    pub fn to_ggf(&self) -> String {
+        let mut s = String::new();
+        s.push_str("(;GM[Othello]");
+        let mut push = |name: &str, value: &str| {
+            let _ = write!(s, "{}[{}]", name, escape(value));
+        };
+        if let Some(place) = &self.place {
+            push("PC", place);
+        }
+        if let Some(date) = &self.date {
+            push("DT", date);
+        }
+        push("PB", &self.black.name);
+        push("PW", &self.white.name);
+        if let Some(rating) = self.black.rating {
+            push("RB", &rating.to_string());
+        }
+        if let Some(rating) = self.white.rating {
+            push("RW", &rating.to_string());
+        }
+        if let Some(time_control) = &self.time_control {
+            push("TI", time_control);
+        }
+        if let Some(time_control) = &self.black.time_control {
+            push("TB", time_control);
+        }
+        if let Some(time_control) = &self.white.time_control {
+            push("TW", time_control);
+        }
+        push("TY", "8");
+        if let Some(result) = &self.result {
+            let reason = match result.reason {
+                ResultReason::Normal => "",
+                ResultReason::Resign => ":r",
+                ResultReason::Timeout => ":t",
+                ResultReason::Agreement => ":s",
+            };
+            match result.disc_diff {
+                Some(disc_diff) => push("RE", &format!("{:+.3}{}", disc_diff, reason)),
+                None => push("RE", "?"),
+            }
+        }
+        for (name, value) in &self.properties {
+            push(name, value);
+        }
+        push("BO", &board_to_ggf(&self.start));
+
+        let mut color = self.start.to_move();
+        for mv in &self.moves {
+            let name = match color {
+                Color::Black => "B",
+                Color::White => "W",
+            };
+            let mut value = match mv.mv {
+                Move::Put(pos) => pos.to_string().to_ascii_lowercase(),
+                Move::Pass => "PA".into(),
+            };
+            if mv.eval.is_some() || mv.time.is_some() {
+                value.push('/');
+                if let Some(eval) = mv.eval {
+                    let _ = write!(value, "{}", eval);
+                }
+            }
+            if let Some(time) = mv.time {
+                let _ = write!(value, "/{}", time);
+            }
+            push(name, &value);
+            color = color.reverse();
+        }
+        s.push_str(";)");
+        s
+    }
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    offset: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.s[self.offset..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    fn next_game(&mut self) -> Result<Option<GameRecord>, ParseGgfError> {
+        match self.rest().find("(;") {
+            Some(start) => self.offset += start + 2,
+            None => return Ok(None),
+        }
+
+        let mut record = GameRecord::default();
+        let mut to_move = record.start.to_move();
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with(";)") {
+                self.offset += 2;
+                return Ok(Some(record));
+            }
+            let (name, value) = self.next_property()?;
+            parse_property(&mut record, &mut to_move, name, &value)?;
+        }
+    }
+
+    fn next_property(&mut self) -> Result<(&str, String), ParseGgfError> {
+        let rest = self.rest();
+        if rest.is_empty() {
+            return Err(ParseGgfError::UnexpectedEnd);
+        }
+        let name_len = rest
+            .find(|ch: char| !ch.is_ascii_uppercase())
+            .unwrap_or(rest.len());
+        if name_len == 0 || !rest[name_len..].starts_with('[') {
+            return Err(ParseGgfError::InvalidProperty(self.offset));
+        }
+        let name = &self.s[self.offset..self.offset + name_len];
+        let value_start = self.offset + name_len + 1;
+
+        let mut value = String::new();
+        let mut chars = self.s[value_start..].char_indices();
+        while let Some((i, ch)) = chars.next() {
+            match ch {
+                ']' => {
+                    self.offset = value_start + i + 1;
+                    return Ok((name, value));
+                }
+                '\\' => match chars.next() {
+                    Some((_, ch)) => value.push(ch),
+                    None => break,
+                },
+                _ => value.push(ch),
+            }
+        }
+        Err(ParseGgfError::UnexpectedEnd)
+    }
+}
+
+fn parse_property(
+    record: &mut GameRecord,
+    to_move: &mut Color,
+    name: &str,
+    value: &str,
+) -> Result<(), ParseGgfError> {
+    let value = value.trim();
+    match name {
+        "GM" => {
+            if !value.eq_ignore_ascii_case("othello") {
+                return Err(ParseGgfError::UnsupportedGame(value.into()));
+            }
+        }
+        "TY" => {
+            if value != "8" {
+                return Err(ParseGgfError::UnsupportedBoardType(value.into()));
+            }
+        }
+        "PC" => record.place = Some(value.into()),
+        "DT" => record.date = Some(value.into()),
+        "PB" => record.black.name = value.into(),
+        "PW" => record.white.name = value.into(),
+        "RB" => record.black.rating = Some(parse_number(name, value)?),
+        "RW" => record.white.rating = Some(parse_number(name, value)?),
+        "TI" => record.time_control = Some(value.into()),
+        "TB" => record.black.time_control = Some(value.into()),
+        "TW" => record.white.time_control = Some(value.into()),
+        "RE" => record.result = parse_result(value)?,
+        "BO" => {
+            record.start = parse_board(value)?;
+            *to_move = record.start.to_move();
+        }
+        "B" | "W" => {
+            let color = if name == "B" {
+                Color::Black
+            } else {
+                Color::White
+            };
+            push_move(&mut record.moves, to_move, color, parse_move(name, value)?);
+        }
+        _ => record.properties.push((name.into(), value.into())),
+    }
+    Ok(())
+}
+
+fn parse_number(name: &str, value: &str) -> Result<f64, ParseGgfError> {
+    value
+        .parse()
+        .map_err(|e| ParseGgfError::InvalidNumber(name.into(), value.into(), e))
+}
+
+fn parse_time(name: &str, value: &str) -> Result<f64, ParseGgfError> {
+    value
+        .split(':')
+        .try_fold(0.0, |acc, part| Ok(acc * 60.0 + parse_number(name, part)?))
+}
+
+fn parse_move(name: &str, value: &str) -> Result<RecordMove, ParseGgfError> {
+    let mut fields = value.split('/').map(str::trim);
+    let pos = fields.next().unwrap_or_default();
+    let mv = if pos.eq_ignore_ascii_case("pa") {
+        Move::Pass
+    } else {
+        Move::Put(
+            pos.parse::<Pos>()
+                .map_err(|e| ParseGgfError::InvalidMove(pos.into(), e))?,
+        )
+    };
+    let eval = match fields.next() {
+        Some(eval) if !eval.is_empty() => Some(parse_number(name, eval)?),
+        _ => None,
+    };
+    let time = match fields.next() {
+        Some(time) if !time.is_empty() => Some(parse_time(name, time)?),
+        _ => None,
+    };
+    Ok(RecordMove {
+        eval,
+        time,
+        ..RecordMove::new(mv)
+    })
+}
+
+fn parse_result(value: &str) -> Result<Option<RecordResult>, ParseGgfError> {
+    if value == "?" || value.is_empty() {
+        return Ok(None);
+    }
+    let (diff, reason) = match value.split_once(':') {
+        Some((diff, reason)) => (diff, reason),
+        None => (value, ""),
+    };
+    let reason = match reason {
+        "" => ResultReason::Normal,
+        "r" => ResultReason::Resign,
+        "t" => ResultReason::Timeout,
+        "s" => ResultReason::Agreement,
+        _ => return Err(ParseGgfError::InvalidResult(value.into())),
+    };
+    let disc_diff = diff
+        .parse()
+        .map_err(|_| ParseGgfError::InvalidResult(value.into()))?;
+    Ok(Some(RecordResult::from_disc_diff(disc_diff, reason)))
+}
+
+fn parse_board(value: &str) -> Result<Position, ParseGgfError> {
+    let invalid = || ParseGgfError::InvalidBoard(value.into());
+    let mut tokens = value.split_ascii_whitespace();
+    if tokens.next() != Some("8") {
+        return Err(invalid());
+    }
+    let cells = tokens.collect::<String>();
+    if cells.len() != 65 {
+        return Err(invalid());
+    }
+    let (cells, side) = cells.split_at(64);
+    let to_move = match side {
+        "*" => Color::Black,
+        "O" => Color::White,
+        _ => return Err(invalid()),
+    };
+
+    let mut position = Position::new(Board::empty(), to_move);
+    for (pos, ch) in Pos::iter_all().zip(cells.chars()) {
+        let color = match ch {
+            '*' => Some(Color::Black),
+            'O' => Some(Color::White),
+            '-' => None,
+            _ => return Err(invalid()),
+        };
+        position.set_disk(pos, color);
+    }
+    Ok(position)
+}
+
+fn board_to_ggf(position: &Position) -> String {
+    let mut s = String::from("8");
+    for (i, pos) in Pos::iter_all().enumerate() {
+        if i % 8 == 0 {
+            s.push(' ');
+        }
+        s.push(match position.get_disk(pos) {
+            Some(Color::Black) => '*',
+            Some(Color::White) => 'O',
+            None => '-',
+        });
+    }
+    s.push_str(match position.to_move() {
+        Color::Black => " *",
+        Color::White => " O",
+    });
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::FULL_GAME, Game, ReplayError};
+
+    const GGS_GAME: &str =
+        "(;GM[Othello]PC[GGS/os]DT[2003.12.15_13:24:03.MST]PB[Saio1200]PW[Saio3000]\
+RB[2197.72]RW[2677.28]TI[15:00//02:00]TY[8]RE[-4.000:r]\
+BO[8 -------- -------- -------- ---O*--- ---*O--- -------- -------- -------- *]\
+B[d3//0.01]W[c5/-2.00/0.01]B[f6/1.50/1:02];)";
+
+    #[test]
+    fn parse() {
+        let record = GameRecord::from_ggf(GGS_GAME).unwrap();
+        assert_eq!(record.place.as_deref(), Some("GGS/os"));
+        assert_eq!(record.black.name, "Saio1200");
+        assert_eq!(record.white.rating, Some(2677.28));
+        assert_eq!(record.time_control.as_deref(), Some("15:00//02:00"));
+        assert_eq!(
+            record.result,
+            Some(RecordResult {
+                winner: Some(Color::White),
+                disc_diff: Some(-4.0),
+                reason: ResultReason::Resign
+            })
+        );
+        assert_eq!(record.start, Position::default());
+        assert_eq!(
+            record
+                .moves
+                .iter()
+                .map(|mv| (mv.mv, mv.eval, mv.time))
+                .collect::<Vec<_>>(),
+            [
+                (Move::Put(Pos::D3), None, Some(0.01)),
+                (Move::Put(Pos::C5), Some(-2.0), Some(0.01)),
+                (Move::Put(Pos::F6), Some(1.5), Some(62.0)),
+            ]
+        );
+        assert_eq!(record.to_game().unwrap().moves().len(), 3);
+
+        let two = format!("{}\n{}\n", GGS_GAME, GGS_GAME);
+        assert_eq!(GameRecord::from_ggf_all(&two).unwrap().len(), 2);
+
+        assert!(matches!(
+            GameRecord::from_ggf("GM[Othello]"),
+            Err(ParseGgfError::MissingStart)
+        ));
+        assert!(matches!(
+            GameRecord::from_ggf(&GGS_GAME[..GGS_GAME.len() - 2]),
+            Err(ParseGgfError::UnexpectedEnd)
+        ));
+        assert!(matches!(
+            GameRecord::from_ggf(&GGS_GAME.replace("TY[8]", "TY[10]")),
+            Err(ParseGgfError::UnsupportedBoardType(_))
+        ));
+        assert!(matches!(
+            GameRecord::from_ggf(&GGS_GAME.replace("c5/", "k5/")),
+            Err(ParseGgfError::InvalidMove(..))
+        ));
+        let record = GameRecord::from_ggf(&GGS_GAME.replace("f6/", "a1/")).unwrap();
+        assert!(matches!(
+            record.to_game(),
+            Err(ReplayError::IllegalMove { index: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        let record = GameRecord::from_ggf(GGS_GAME).unwrap();
+        let ggf = record.to_ggf();
+        assert_eq!(GameRecord::from_ggf(&ggf).unwrap(), record);
+
+        // `]` and `\` in values are escaped
+        let mut escaped = record.clone();
+        escaped.black.name = "Saio [1200]".into();
+        escaped.properties.push(("KM".into(), "C:\\games".into()));
+        escaped.white.name = "Saio\\".into();
+        let ggf = escaped.to_ggf();
+        assert!(ggf.contains("PB[Saio [1200\\]]"));
+        assert_eq!(GameRecord::from_ggf(&ggf).unwrap(), escaped);
+
+        // evaluations and times keep their full precision
+        let precise = GGS_GAME.replace("c5/-2.00/0.01", "c5/-2.125/0.0625");
+        let record = GameRecord::from_ggf(&precise).unwrap();
+        assert_eq!(record.moves[1].eval, Some(-2.125));
+        let ggf = record.to_ggf();
+        assert!(ggf.contains("W[c5/-2.125/0.0625]"));
+        assert_eq!(GameRecord::from_ggf(&ggf).unwrap(), record);
+
+        let game = Game::from_transcript(FULL_GAME).unwrap();
+        let record = GameRecord::from_game(&game);
+        assert_eq!(record.result.unwrap().disc_diff, Some(-26.0));
+        let ggf = record.to_ggf();
+        assert!(ggf.contains("RE[-26.000]"));
+        let parsed = GameRecord::from_ggf(&ggf).unwrap();
+        assert_eq!(parsed, record);
+        assert_eq!(parsed.to_game().unwrap().moves(), game.moves());
+
+        // a missing pass is restored from the colors of the moves
+        let without_pass = ggf.replacen("]W[PA]", "]", 1).replacen("]B[PA]", "]", 1);
+        assert_ne!(without_pass, ggf);
+        assert_eq!(GameRecord::from_ggf(&without_pass).unwrap(), parsed);
+
+        // including one before the first move, if the side to move in `BO` cannot play
+        let record = GameRecord::from_ggf(
+            "(;GM[Othello]BO[8 *------- -O------ -------- ---OO--- ---OO--- -------- -------- \
+             -------- O]B[c3]B[f6];)",
+        )
+        .unwrap();
+        assert_eq!(
+            record.moves.iter().map(|mv| mv.mv).collect::<Vec<_>>(),
+            [
+                Move::Pass,
+                Move::Put(Pos::C3),
+                Move::Pass,
+                Move::Put(Pos::F6)
+            ]
+        );
+        assert_eq!(record.to_game().unwrap().count_disk(Some(Color::Black)), 6);
+    }
+}
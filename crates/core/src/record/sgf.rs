@@ -0,0 +1,612 @@
+use super::{escape, push_move, GameRecord, RecordMove, RecordResult, ResultReason};
+use crate::{Board, Color, Move, Pos, Position};
+use std::{fmt::Write, num::ParseFloatError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseSgfError {
+    #[error("game tree not found")]
+    MissingGameTree,
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+    #[error("unexpected character `{0}` at offset {1}")]
+    UnexpectedChar(char, usize),
+    #[error("unsupported game `{0}`")]
+    UnsupportedGame(String),
+    #[error("unsupported board size `{0}`")]
+    UnsupportedBoardSize(String),
+    #[error("invalid point `{0}`")]
+    InvalidPoint(String),
+    #[error("invalid number `{1}` in {0}: {2}")]
+    InvalidNumber(String, String, ParseFloatError),
+    #[error("invalid color `{0}`")]
+    InvalidColor(String),
+    #[error("invalid result `{0}`")]
+    InvalidResult(String),
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    properties: Vec<(String, Vec<String>)>,
+}
+
+#[derive(Debug, Default)]
+struct Tree {
+    nodes: Vec<Node>,
+    children: Vec<Tree>,
+}
+
+impl GameRecord {
+    pub fn from_sgf(s: &str) -> Result<Self, ParseSgfError> {
+        let mut parser = Parser { s, offset: 0 };
+        let tree = parser.next_tree()?.ok_or(ParseSgfError::MissingGameTree)?;
+        tree_to_record(&tree)
+    }
+
+    pub fn from_sgf_all(s: &str) -> Result<Vec<Self>, ParseSgfError> {
+        let mut parser = Parser { s, offset: 0 };
+        let mut records = vec![];
+        while let Some(tree) = parser.next_tree()? {
+            records.push(tree_to_record(&tree)?);
+        }
+        Ok(records)
+    }
+
+    // the export is lossy: SGF has no notation for agreed results, which are written as
+    // plain disc differences, nor for the time spent on a move, which is dropped
+    pub fn to_sgf(&self) -> String {
+        let mut s = String::from("(;GM[2]FF[4]SZ[8]");
+        let mut push = |name: &str, value: &str| {
+            let _ = write!(s, "{}[{}]", name, escape(value));
+        };
+        if !self.black.name.is_empty() {
+            push("PB", &self.black.name);
+        }
+        if !self.white.name.is_empty() {
+            push("PW", &self.white.name);
+        }
+        if let Some(rating) = self.black.rating {
+            push("BR", &rating.to_string());
+        }
+        if let Some(rating) = self.white.rating {
+            push("WR", &rating.to_string());
+        }
+        if let Some(event) = &self.event {
+            push("EV", event);
+        }
+        if let Some(place) = &self.place {
+            push("PC", place);
+        }
+        if let Some(date) = &self.date {
+            push("DT", date);
+        }
+        if let Some(time_control) = &self.time_control {
+            push("TM", time_control);
+        }
+        if let Some(result) = &self.result {
+            push("RE", &result_to_sgf(result));
+        }
+        if let Some(comment) = &self.comment {
+            push("C", comment);
+        }
+        for (name, value) in &self.properties {
+            push(name, value);
+        }
+        if self.start != Position::default() {
+            for (name, color) in [("AB", Color::Black), ("AW", Color::White)] {
+                let points = Pos::iter_all()
+                    .filter(|&pos| self.start.get_disk(pos) == Some(color))
+                    .collect::<Vec<_>>();
+                if !points.is_empty() {
+                    s.push_str(name);
+                    for pos in points {
+                        let _ = write!(s, "[{}]", point_to_sgf(pos));
+                    }
+                }
+            }
+            s.push_str(match self.start.to_move() {
+                Color::Black => "PL[B]",
+                Color::White => "PL[W]",
+            });
+        }
+        write_line(&mut s, &self.moves, self.start.to_move());
+        s.push(')');
+        s
+    }
+}
+
+fn point_to_sgf(pos: Pos) -> String {
+    let x = char::from(b'a' + pos.x() as u8);
+    let y = char::from(b'a' + pos.y() as u8);
+    format!("{}{}", x, y)
+}
+
+fn result_to_sgf(result: &RecordResult) -> String {
+    let winner = match result.winner {
+        Some(Color::Black) => 'B',
+        Some(Color::White) => 'W',
+        None => return "0".into(),
+    };
+    let margin = match (result.reason, result.disc_diff) {
+        (ResultReason::Resign, _) => "R".into(),
+        (ResultReason::Timeout, _) => "T".into(),
+        (_, Some(disc_diff)) => disc_diff.abs().to_string(),
+        (_, None) => return "?".into(),
+    };
+    format!("{}+{}", winner, margin)
+}
+
+fn write_line(s: &mut String, moves: &[RecordMove], mut color: Color) {
+    for (i, mv) in moves.iter().enumerate() {
+        if !mv.variations.is_empty() {
+            s.push('(');
+            write_node(s, mv, color);
+            write_line(s, &moves[i + 1..], color.reverse());
+            s.push(')');
+            for variation in &mv.variations {
+                s.push('(');
+                write_line(s, variation, color);
+                s.push(')');
+            }
+            return;
+        }
+        write_node(s, mv, color);
+        color = color.reverse();
+    }
+}
+
+fn write_node(s: &mut String, mv: &RecordMove, color: Color) {
+    let name = match color {
+        Color::Black => 'B',
+        Color::White => 'W',
+    };
+    let point = match mv.mv {
+        Move::Put(pos) => point_to_sgf(pos),
+        Move::Pass => String::new(),
+    };
+    let _ = write!(s, ";{}[{}]", name, point);
+    if let Some(eval) = mv.eval {
+        let _ = write!(s, "V[{}]", eval);
+    }
+    if let Some(comment) = &mv.comment {
+        let _ = write!(s, "C[{}]", escape(comment));
+    }
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    offset: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.s[self.offset..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Result<char, ParseSgfError> {
+        self.skip_whitespace();
+        self.rest()
+            .chars()
+            .next()
+            .ok_or(ParseSgfError::UnexpectedEnd)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseSgfError> {
+        let ch = self.peek()?;
+        if ch != expected {
+            return Err(ParseSgfError::UnexpectedChar(ch, self.offset));
+        }
+        self.offset += ch.len_utf8();
+        Ok(())
+    }
+
+    fn next_tree(&mut self) -> Result<Option<Tree>, ParseSgfError> {
+        match self.rest().find('(') {
+            Some(start) => self.offset += start,
+            None => return Ok(None),
+        }
+        self.tree().map(Some)
+    }
+
+    fn tree(&mut self) -> Result<Tree, ParseSgfError> {
+        self.expect('(')?;
+        let mut tree = Tree::default();
+        while self.peek()? == ';' {
+            self.offset += 1;
+            tree.nodes.push(self.node()?);
+        }
+        while self.peek()? == '(' {
+            tree.children.push(self.tree()?);
+        }
+        self.expect(')')?;
+        Ok(tree)
+    }
+
+    fn node(&mut self) -> Result<Node, ParseSgfError> {
+        let mut node = Node::default();
+        while self.peek()?.is_ascii_uppercase() {
+            let rest = self.rest();
+            let len = rest
+                .find(|ch: char| !ch.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            // FF[3] allows lowercase letters in property names, which are ignored
+            let name = rest[..len]
+                .chars()
+                .filter(char::is_ascii_uppercase)
+                .collect::<String>();
+            self.offset += len;
+
+            let mut values = vec![];
+            while self.peek()? == '[' {
+                self.offset += 1;
+                values.push(self.value()?);
+            }
+            if values.is_empty() {
+                let ch = self.peek()?;
+                return Err(ParseSgfError::UnexpectedChar(ch, self.offset));
+            }
+            node.properties.push((name, values));
+        }
+        Ok(node)
+    }
+
+    fn value(&mut self) -> Result<String, ParseSgfError> {
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, ch)) = chars.next() {
+            match ch {
+                ']' => {
+                    self.offset += i + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    // soft line break
+                    Some((_, '\n')) => {}
+                    Some((_, ch)) => value.push(ch),
+                    None => break,
+                },
+                _ => value.push(ch),
+            }
+        }
+        Err(ParseSgfError::UnexpectedEnd)
+    }
+}
+
+fn tree_to_record(tree: &Tree) -> Result<GameRecord, ParseSgfError> {
+    let mut record = GameRecord::default();
+    let root = tree.nodes.first().ok_or(ParseSgfError::MissingGameTree)?;
+
+    let mut setup = None::<Position>;
+    let mut to_move = None;
+    for (name, values) in &root.properties {
+        let value = values[0].trim();
+        match name.as_str() {
+            "GM" => {
+                if value != "2" {
+                    return Err(ParseSgfError::UnsupportedGame(value.into()));
+                }
+            }
+            "SZ" => {
+                if value != "8" {
+                    return Err(ParseSgfError::UnsupportedBoardSize(value.into()));
+                }
+            }
+            "FF" | "CA" | "AP" | "B" | "W" | "V" => {}
+            "PB" => record.black.name = value.into(),
+            "PW" => record.white.name = value.into(),
+            "BR" => record.black.rating = Some(parse_number(name, value)?),
+            "WR" => record.white.rating = Some(parse_number(name, value)?),
+            "EV" => record.event = Some(value.into()),
+            "PC" => record.place = Some(value.into()),
+            "DT" => record.date = Some(value.into()),
+            "TM" => record.time_control = Some(value.into()),
+            "RE" => record.result = parse_result(value)?,
+            "C" => record.comment = Some(values[0].clone()),
+            "PL" => to_move = Some(parse_color(value)?),
+            "AB" | "AW" | "AE" => {
+                let color = match name.as_str() {
+                    "AB" => Some(Color::Black),
+                    "AW" => Some(Color::White),
+                    _ => None,
+                };
+                let position =
+                    setup.get_or_insert_with(|| Position::new(Board::empty(), Color::Black));
+                for value in values {
+                    position.set_disk(parse_point(value)?, color);
+                }
+            }
+            _ => {
+                for value in values {
+                    record.properties.push((name.clone(), value.clone()));
+                }
+            }
+        }
+    }
+
+    let to_move = to_move
+        .or_else(|| first_color(tree))
+        .unwrap_or(Color::Black);
+    record.start = match setup {
+        Some(position) if position.to_move() == to_move => position,
+        Some(position) => position.pass(),
+        None if to_move == Color::Black => Position::default(),
+        None => Position::default().pass(),
+    };
+    collect_line(&tree.nodes, &tree.children, &mut record.moves, to_move)?;
+    Ok(record)
+}
+
+fn first_color(tree: &Tree) -> Option<Color> {
+    let color = tree
+        .nodes
+        .iter()
+        .flat_map(|node| &node.properties)
+        .find_map(|(name, _)| match name.as_str() {
+            "B" => Some(Color::Black),
+            "W" => Some(Color::White),
+            _ => None,
+        });
+    color.or_else(|| tree.children.first().and_then(first_color))
+}
+
+fn collect_line(
+    nodes: &[Node],
+    children: &[Tree],
+    moves: &mut Vec<RecordMove>,
+    mut to_move: Color,
+) -> Result<(), ParseSgfError> {
+    for node in nodes {
+        let mut mv = None;
+        let mut comment = None;
+        let mut eval = None;
+        for (name, values) in &node.properties {
+            match name.as_str() {
+                "B" => mv = Some((Color::Black, parse_move(&values[0])?)),
+                "W" => mv = Some((Color::White, parse_move(&values[0])?)),
+                "C" => comment = Some(values[0].clone()),
+                "V" => eval = Some(parse_number(name, values[0].trim())?),
+                _ => {}
+            }
+        }
+        match mv {
+            Some((color, mv)) => {
+                let mv = RecordMove {
+                    eval,
+                    comment,
+                    ..RecordMove::new(mv)
+                };
+                push_move(moves, &mut to_move, color, mv);
+            }
+            None => {
+                if let (Some(last), Some(comment)) = (moves.last_mut(), comment) {
+                    last.comment = Some(comment);
+                }
+            }
+        }
+    }
+
+    if let Some((main, rest)) = children.split_first() {
+        let branch = moves.len();
+        collect_line(&main.nodes, &main.children, moves, to_move)?;
+        for child in rest {
+            let mut variation = vec![];
+            collect_line(&child.nodes, &child.children, &mut variation, to_move)?;
+            match moves.get_mut(branch) {
+                Some(mv) => {
+                    if !variation.is_empty() {
+                        mv.variations.push(variation);
+                    }
+                }
+                // the main line ends without a move, so the variation continues it instead
+                None => moves.extend(variation),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_number(name: &str, value: &str) -> Result<f64, ParseSgfError> {
+    value
+        .parse()
+        .map_err(|e| ParseSgfError::InvalidNumber(name.into(), value.into(), e))
+}
+
+fn parse_color(value: &str) -> Result<Color, ParseSgfError> {
+    match value {
+        "B" | "b" => Ok(Color::Black),
+        "W" | "w" => Ok(Color::White),
+        _ => Err(ParseSgfError::InvalidColor(value.into())),
+    }
+}
+
+fn parse_point(value: &str) -> Result<Pos, ParseSgfError> {
+    let value = value.trim();
+    let invalid = || ParseSgfError::InvalidPoint(value.into());
+    let bytes = value.as_bytes();
+    match bytes {
+        [x @ b'a'..=b'h', y @ b'a'..=b'h'] => {
+            Pos::from_xy((x - b'a') as i8, (y - b'a') as i8).ok_or_else(invalid)
+        }
+        // some Othello programs write moves as `f5`
+        [_, b'1'..=b'8'] => value.parse().map_err(|_| invalid()),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_move(value: &str) -> Result<Move, ParseSgfError> {
+    let value = value.trim();
+    if value.is_empty() || value == "tt" || value.eq_ignore_ascii_case("pass") {
+        return Ok(Move::Pass);
+    }
+    parse_point(value).map(Move::Put)
+}
+
+fn parse_result(value: &str) -> Result<Option<RecordResult>, ParseSgfError> {
+    let invalid = || ParseSgfError::InvalidResult(value.into());
+    match value {
+        "" | "?" | "Void" => return Ok(None),
+        "0" | "Draw" | "D" => {
+            return Ok(Some(RecordResult::from_disc_diff(
+                0.0,
+                ResultReason::Normal,
+            )))
+        }
+        _ => {}
+    }
+    let (winner, margin) = value.split_once('+').ok_or_else(invalid)?;
+    let winner = match winner {
+        "B" => Color::Black,
+        "W" => Color::White,
+        _ => return Err(invalid()),
+    };
+    let reason = match margin {
+        "R" | "Resign" => ResultReason::Resign,
+        "T" | "Time" => ResultReason::Timeout,
+        _ => ResultReason::Normal,
+    };
+    let disc_diff = match (reason, margin) {
+        (ResultReason::Normal, "") => None,
+        (ResultReason::Normal, margin) => {
+            let margin = margin.parse::<f64>().map_err(|_| invalid())?;
+            Some(match winner {
+                Color::Black => margin,
+                Color::White => -margin,
+            })
+        }
+        _ => None,
+    };
+    Ok(Some(RecordResult {
+        winner: Some(winner),
+        disc_diff,
+        reason,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::FULL_GAME, Game, ReplayError};
+
+    const SGF: &str = "(;GM[2]FF[4]SZ[8]PB[Alice]PW[Bob \\[2d\\]]RE[W+R]C[opening study]
+;B[fe]C[parallel?];W[fd]V[-1.5]
+(;B[ec];W[ff])
+(;B[dc]C[d3 instead]
+  (;W[cd])
+  (;W[ff]))
+(;B[gc]))";
+
+    #[test]
+    fn parse() {
+        let record = GameRecord::from_sgf(SGF).unwrap();
+        assert_eq!(record.black.name, "Alice");
+        assert_eq!(record.white.name, "Bob [2d]");
+        assert_eq!(record.comment.as_deref(), Some("opening study"));
+        assert_eq!(
+            record.result,
+            Some(RecordResult {
+                winner: Some(Color::White),
+                disc_diff: None,
+                reason: ResultReason::Resign,
+            })
+        );
+        assert_eq!(record.start, Position::default());
+
+        let main = record.moves.iter().map(|mv| mv.mv).collect::<Vec<_>>();
+        assert_eq!(main, [Pos::F5, Pos::F4, Pos::E3, Pos::F6].map(Move::Put));
+        assert_eq!(record.moves[0].comment.as_deref(), Some("parallel?"));
+        assert_eq!(record.moves[1].eval, Some(-1.5));
+
+        let variations = &record.moves[2].variations;
+        assert_eq!(variations.len(), 2);
+        assert_eq!(variations[0][0].mv, Move::Put(Pos::D3));
+        assert_eq!(variations[0][0].comment.as_deref(), Some("d3 instead"));
+        assert_eq!(variations[0][1].mv, Move::Put(Pos::C4));
+        assert_eq!(variations[0][1].variations[0][0].mv, Move::Put(Pos::F6));
+        assert_eq!(variations[1].len(), 1);
+
+        assert_eq!(record.to_game().unwrap().moves().len(), 4);
+
+        assert_eq!(
+            GameRecord::from_sgf_all(&format!("{}{}", SGF, SGF))
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(matches!(
+            GameRecord::from_sgf(&SGF.replace("GM[2]", "GM[1]")),
+            Err(ParseSgfError::UnsupportedGame(_))
+        ));
+        assert!(matches!(
+            GameRecord::from_sgf(&SGF[..SGF.len() - 1]),
+            Err(ParseSgfError::UnexpectedEnd)
+        ));
+        assert!(matches!(
+            GameRecord::from_sgf(&SGF.replace("B[fe]", "B[fz]")),
+            Err(ParseSgfError::InvalidPoint(_))
+        ));
+        let record = GameRecord::from_sgf(&SGF.replace("W[fd]", "W[aa]")).unwrap();
+        assert!(matches!(
+            record.to_game(),
+            Err(ReplayError::IllegalMove { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn variation_after_empty_main_line() {
+        // a variation after a main line without moves continues the main line
+        let continued =
+            GameRecord::from_sgf("(;GM[2];B[fe](;C[x])(;W[fd](;B[ec])(;B[dc])))").unwrap();
+        let main = continued.moves.iter().map(|mv| mv.mv).collect::<Vec<_>>();
+        assert_eq!(main, [Pos::F5, Pos::F4, Pos::E3].map(Move::Put));
+        assert_eq!(continued.moves[0].comment.as_deref(), Some("x"));
+        assert_eq!(
+            continued.moves[2].variations,
+            [vec![RecordMove::new(Move::Put(Pos::D3))]]
+        );
+        assert_eq!(
+            GameRecord::from_sgf(&continued.to_sgf()).unwrap(),
+            continued
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let record = GameRecord::from_sgf(SGF).unwrap();
+        assert_eq!(GameRecord::from_sgf(&record.to_sgf()).unwrap(), record);
+
+        let game = Game::from_transcript(FULL_GAME).unwrap();
+        let record = GameRecord::from_game(&game);
+        let sgf = record.to_sgf();
+        assert!(sgf.contains("RE[W+26]"));
+        let parsed = GameRecord::from_sgf(&sgf).unwrap();
+        assert_eq!(parsed, record);
+        assert_eq!(parsed.to_game().unwrap().moves(), game.moves());
+
+        // setup with white to move, written as `f5`-style points
+        let record =
+            GameRecord::from_sgf("(;GM[2]AB[d4][e4][f4][d5]AW[e5]PL[W];W[e3];B[f6])").unwrap();
+        let game = record.to_game().unwrap();
+        assert_eq!(game.start_position().to_move(), Color::White);
+        assert_eq!(game.start_position().get_disk(Pos::F4), Some(Color::Black));
+        assert_eq!(game.count_disk(Some(Color::Black)), 5);
+        assert_eq!(GameRecord::from_sgf(&record.to_sgf()).unwrap(), record);
+
+        // omitted passes are restored, including one before the first move
+        let record =
+            GameRecord::from_sgf("(;GM[2]AB[aa]AW[bb][dd][ed][de][ee]PL[W];B[cc];B[ff])").unwrap();
+        assert_eq!(
+            record.moves.iter().map(|mv| mv.mv).collect::<Vec<_>>(),
+            [
+                Move::Pass,
+                Move::Put(Pos::C3),
+                Move::Pass,
+                Move::Put(Pos::F6)
+            ]
+        );
+        assert_eq!(record.to_game().unwrap().count_disk(Some(Color::Black)), 6);
+    }
+}
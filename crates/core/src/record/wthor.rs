@@ -0,0 +1,279 @@
+use super::{GameRecord, RecordResult, ResultReason};
+use crate::{Game, Pos, ReplayError};
+use std::io::{self, Read};
+
+const HEADER_LEN: usize = 16;
+const GAME_LEN: usize = 68;
+const PLAYER_LEN: usize = 20;
+const TOURNAMENT_LEN: usize = 26;
+const MAX_RESERVED_GAMES: usize = 1 << 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WthorHeader {
+    pub created: (u16, u8, u8),
+    pub game_count: u32,
+    pub record_count: u16,
+    pub year: u16,
+    pub board_size: u8,
+    pub game_type: u8,
+    pub depth: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WthorGame {
+    pub tournament: u16,
+    pub black: u16,
+    pub white: u16,
+    pub black_disks: u8,
+    pub theoretical_black_disks: u8,
+    pub moves: Vec<Pos>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WthorDatabase {
+    pub header: WthorHeader,
+    pub games: Vec<WthorGame>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReadWthorError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("unsupported board size {0}")]
+    UnsupportedBoardSize(u8),
+    #[error("invalid move byte {byte} at game {game}, index {index}")]
+    InvalidMove { game: usize, index: usize, byte: u8 },
+}
+
+impl WthorHeader {
+    fn read(mut reader: impl Read) -> io::Result<Self> {
+        let mut buf = [0; HEADER_LEN];
+        reader.read_exact(&mut buf)?;
+        Ok(Self {
+            created: (u16::from(buf[0]) * 100 + u16::from(buf[1]), buf[2], buf[3]),
+            game_count: u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]),
+            record_count: u16::from_le_bytes([buf[8], buf[9]]),
+            year: u16::from_le_bytes([buf[10], buf[11]]),
+            board_size: buf[12],
+            game_type: buf[13],
+            depth: buf[14],
+        })
+    }
+}
+
+impl WthorDatabase {
+    pub fn read(mut reader: impl Read) -> Result<Self, ReadWthorError> {
+        let header = WthorHeader::read(&mut reader)?;
+        if header.board_size != 0 && header.board_size != 8 {
+            return Err(ReadWthorError::UnsupportedBoardSize(header.board_size));
+        }
+
+        // the count comes from the file, so it only hints the capacity
+        let mut games = Vec::with_capacity((header.game_count as usize).min(MAX_RESERVED_GAMES));
+        let mut buf = [0; GAME_LEN];
+        for game in 0..header.game_count as usize {
+            reader.read_exact(&mut buf)?;
+            let mut moves = Vec::with_capacity(60);
+            for (index, &byte) in buf[8..].iter().enumerate() {
+                if byte == 0 {
+                    break;
+                }
+                let (row, col) = (byte / 10, byte % 10);
+                let pos = Pos::from_xy(col as i8 - 1, row as i8 - 1)
+                    .ok_or(ReadWthorError::InvalidMove { game, index, byte })?;
+                moves.push(pos);
+            }
+            games.push(WthorGame {
+                tournament: u16::from_le_bytes([buf[0], buf[1]]),
+                black: u16::from_le_bytes([buf[2], buf[3]]),
+                white: u16::from_le_bytes([buf[4], buf[5]]),
+                black_disks: buf[6],
+                theoretical_black_disks: buf[7],
+                moves,
+            });
+        }
+        Ok(Self { header, games })
+    }
+
+    pub fn to_records(
+        &self,
+        players: &[String],
+        tournaments: &[String],
+    ) -> Result<Vec<GameRecord>, ReplayError> {
+        self.games
+            .iter()
+            .map(|game| {
+                let mut record = game.to_record(players, tournaments)?;
+                record.date = Some(self.header.year.to_string());
+                Ok(record)
+            })
+            .collect()
+    }
+}
+
+pub fn read_wthor_players(reader: impl Read) -> Result<Vec<String>, ReadWthorError> {
+    read_names(reader, PLAYER_LEN)
+}
+
+pub fn read_wthor_tournaments(reader: impl Read) -> Result<Vec<String>, ReadWthorError> {
+    read_names(reader, TOURNAMENT_LEN)
+}
+
+fn read_names(mut reader: impl Read, len: usize) -> Result<Vec<String>, ReadWthorError> {
+    let header = WthorHeader::read(&mut reader)?;
+    let mut names = Vec::with_capacity(header.record_count.into());
+    let mut buf = vec![0; len];
+    for _ in 0..header.record_count {
+        reader.read_exact(&mut buf)?;
+        // names are NUL-terminated ISO-8859-1
+        let name = buf
+            .iter()
+            .take_while(|&&byte| byte != 0)
+            .map(|&byte| char::from(byte))
+            .collect::<String>();
+        names.push(name.trim_end().into());
+    }
+    Ok(names)
+}
+
+impl WthorGame {
+    pub fn to_game(&self) -> Result<Game, ReplayError> {
+        let mut game = Game::new();
+        for (index, &pos) in self.moves.iter().enumerate() {
+            game.put_disk(pos)
+                .map_err(|source| ReplayError::IllegalMove { index, source })?;
+        }
+        Ok(game)
+    }
+
+    pub fn to_record(
+        &self,
+        players: &[String],
+        tournaments: &[String],
+    ) -> Result<GameRecord, ReplayError> {
+        let game = self.to_game()?;
+        let name = |names: &[String], index: u16| names.get(usize::from(index)).cloned();
+
+        let mut record = GameRecord::from_game(&game);
+        record.event = name(tournaments, self.tournament);
+        record.black.name = name(players, self.black).unwrap_or_default();
+        record.white.name = name(players, self.white).unwrap_or_default();
+        // the stored score counts black disks with empty squares given to the winner
+        record.result = Some(RecordResult::from_disc_diff(
+            f64::from(i32::from(self.black_disks) * 2 - 64),
+            ResultReason::Normal,
+        ));
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::FULL_GAME, Color};
+
+    fn header(game_count: u32, record_count: u16) -> Vec<u8> {
+        let mut buf = vec![20, 21, 12, 31];
+        buf.extend(game_count.to_le_bytes());
+        buf.extend(record_count.to_le_bytes());
+        buf.extend(2021u16.to_le_bytes());
+        buf.extend([8, 0, 22, 0]);
+        buf
+    }
+
+    fn names(names: &[&str], len: usize) -> Vec<u8> {
+        let mut buf = header(0, names.len() as u16);
+        for name in names {
+            let mut record = name.chars().map(|ch| ch as u8).collect::<Vec<_>>();
+            record.resize(len, 0);
+            buf.extend(record);
+        }
+        buf
+    }
+
+    fn wtb(moves: &[Pos]) -> Vec<u8> {
+        let mut buf = header(1, 0);
+        buf.extend(1u16.to_le_bytes());
+        buf.extend(0u16.to_le_bytes());
+        buf.extend(2u16.to_le_bytes());
+        buf.extend([19, 20]);
+        let mut move_bytes = moves
+            .iter()
+            .map(|pos| 10 * (pos.y() as u8 + 1) + pos.x() as u8 + 1)
+            .collect::<Vec<_>>();
+        move_bytes.resize(60, 0);
+        buf.extend(move_bytes);
+        buf
+    }
+
+    #[test]
+    fn read() {
+        let game = Game::from_transcript(FULL_GAME).unwrap();
+        let moves = game
+            .moves()
+            .iter()
+            .filter_map(|record| match record.mv {
+                crate::Move::Put(pos) => Some(pos),
+                crate::Move::Pass => None,
+            })
+            .collect::<Vec<_>>();
+
+        let db = WthorDatabase::read(wtb(&moves).as_slice()).unwrap();
+        assert_eq!(db.header.created, (2021, 12, 31));
+        assert_eq!(db.header.year, 2021);
+        assert_eq!(db.header.depth, 22);
+        assert_eq!(db.games.len(), 1);
+        assert_eq!(db.games[0].moves, moves);
+        assert_eq!(db.games[0].to_game().unwrap().moves(), game.moves());
+
+        let players =
+            read_wthor_players(names(&["Alice", "Bob", "\u{e9}ric"], PLAYER_LEN).as_slice())
+                .unwrap();
+        assert_eq!(players, ["Alice", "Bob", "\u{e9}ric"]);
+        let tournaments =
+            read_wthor_tournaments(names(&["", "Championnat"], TOURNAMENT_LEN).as_slice()).unwrap();
+
+        let records = db.to_records(&players, &tournaments).unwrap();
+        assert_eq!(records[0].event.as_deref(), Some("Championnat"));
+        assert_eq!(records[0].black.name, "Alice");
+        assert_eq!(records[0].white.name, "\u{e9}ric");
+        assert_eq!(records[0].date.as_deref(), Some("2021"));
+        assert_eq!(records[0].result.unwrap().disc_diff, Some(-26.0));
+        assert_eq!(
+            records[0].to_game().unwrap().count_disk(Some(Color::White)),
+            45
+        );
+    }
+
+    #[test]
+    fn reject() {
+        let mut buf = wtb(&[Pos::F5]);
+        buf[HEADER_LEN + 8] = 59;
+        assert!(matches!(
+            WthorDatabase::read(buf.as_slice()),
+            Err(ReadWthorError::InvalidMove {
+                game: 0,
+                index: 0,
+                byte: 59
+            })
+        ));
+
+        let buf = wtb(&[Pos::F5]);
+        assert!(matches!(
+            WthorDatabase::read(&buf[..buf.len() - 1]),
+            Err(ReadWthorError::Io(_))
+        ));
+
+        // a corrupt count fails on reading instead of allocating
+        assert!(matches!(
+            WthorDatabase::read(header(u32::MAX, 0).as_slice()),
+            Err(ReadWthorError::Io(_))
+        ));
+
+        let db = WthorDatabase::read(wtb(&[Pos::F5, Pos::A1]).as_slice()).unwrap();
+        assert!(matches!(
+            db.games[0].to_game(),
+            Err(ReplayError::IllegalMove { index: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,382 @@
+use crate::{Color, Game, Move, ParsePosError, ParsePositionError, Pos, Position, ReplayError};
+use std::{
+    io::{self, BufRead, Write},
+    num::ParseIntError,
+    time::Duration,
+};
+
+const MAGIC: &str = "reversi-save";
+
+#[derive(Debug, Clone)]
+pub struct SaveData {
+    pub game: Game,
+    pub black: SavedPlayer,
+    pub white: SavedPlayer,
+    pub clock: Option<SavedClock>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedPlayer {
+    pub kind: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SavedClock {
+    pub black: Duration,
+    pub white: Duration,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReadSaveError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("not a save file")]
+    MissingHeader,
+    #[error("unsupported save format version `{0}`")]
+    UnsupportedVersion(String),
+    #[error("unknown field `{key}` at line {line}")]
+    UnknownField { line: usize, key: String },
+    #[error("duplicated field `{key}` at line {line}")]
+    DuplicatedField { line: usize, key: String },
+    #[error("missing field `{0}`")]
+    MissingField(&'static str),
+    #[error("invalid position at line {line}: {source}")]
+    Position {
+        line: usize,
+        #[source]
+        source: ParsePositionError,
+    },
+    #[error("invalid move `{mv}` at line {line}: {source}")]
+    Move {
+        line: usize,
+        mv: String,
+        #[source]
+        source: ParsePosError,
+    },
+    #[error("invalid player at line {line}")]
+    Player { line: usize },
+    #[error("invalid clock at line {line}")]
+    Clock { line: usize },
+    #[error("invalid clock time `{value}` at line {line}: {source}")]
+    ClockTime {
+        line: usize,
+        value: String,
+        #[source]
+        source: ParseIntError,
+    },
+    #[error("cannot replay moves: {0}")]
+    Replay(#[from] ReplayError),
+}
+
+impl SaveData {
+    pub const VERSION: u32 = 1;
+
+    pub fn new(game: Game, black: SavedPlayer, white: SavedPlayer) -> Self {
+        Self {
+            game,
+            black,
+            white,
+            clock: None,
+        }
+    }
+
+    pub fn player(&self, color: Color) -> &SavedPlayer {
+        match color {
+            Color::Black => &self.black,
+            Color::White => &self.white,
+        }
+    }
+
+    /// Fails with `io::ErrorKind::InvalidInput` if a player cannot be read back as written.
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        check_player(&self.black)?;
+        check_player(&self.white)?;
+
+        writeln!(writer, "{} {}", MAGIC, Self::VERSION)?;
+        writeln!(writer, "position {}", self.game.start_position())?;
+        write!(writer, "moves")?;
+        for record in self.game.moves() {
+            match record.mv {
+                Move::Put(pos) => write!(writer, " {}", pos.to_string().to_ascii_lowercase())?,
+                Move::Pass => write!(writer, " pass")?,
+            }
+        }
+        writeln!(writer)?;
+        for (key, player) in [("black", &self.black), ("white", &self.white)] {
+            writeln!(writer, "{} {} {}", key, player.kind, player.name)?;
+        }
+        if let Some(clock) = &self.clock {
+            writeln!(
+                writer,
+                "clock {} {}",
+                clock.black.as_millis(),
+                clock.white.as_millis()
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn read(reader: impl BufRead) -> Result<Self, ReadSaveError> {
+        let mut lines = reader.lines();
+
+        let header = lines.next().ok_or(ReadSaveError::MissingHeader)??;
+        let version = match header.trim().split_once(' ') {
+            Some((MAGIC, version)) => version.trim(),
+            _ => return Err(ReadSaveError::MissingHeader),
+        };
+        if version != Self::VERSION.to_string() {
+            return Err(ReadSaveError::UnsupportedVersion(version.into()));
+        }
+
+        let mut position = None;
+        let mut moves = None;
+        let mut black = None;
+        let mut white = None;
+        let mut clock = None;
+
+        for (idx, line) in lines.enumerate() {
+            let line_no = idx + 2;
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let value = value.trim();
+            let duplicated = match key {
+                "position" => position
+                    .replace(value.parse::<Position>().map_err(|source| {
+                        ReadSaveError::Position {
+                            line: line_no,
+                            source,
+                        }
+                    })?)
+                    .is_some(),
+                "moves" => moves.replace(parse_moves(line_no, value)?).is_some(),
+                "black" => black.replace(parse_player(line_no, value)?).is_some(),
+                "white" => white.replace(parse_player(line_no, value)?).is_some(),
+                "clock" => clock.replace(parse_clock(line_no, value)?).is_some(),
+                _ => {
+                    return Err(ReadSaveError::UnknownField {
+                        line: line_no,
+                        key: key.into(),
+                    })
+                }
+            };
+            if duplicated {
+                return Err(ReadSaveError::DuplicatedField {
+                    line: line_no,
+                    key: key.into(),
+                });
+            }
+        }
+
+        let position = position.ok_or(ReadSaveError::MissingField("position"))?;
+        let moves = moves.ok_or(ReadSaveError::MissingField("moves"))?;
+        let game = Game::replay(position, &moves)?;
+        Ok(Self {
+            game,
+            black: black.ok_or(ReadSaveError::MissingField("black"))?,
+            white: white.ok_or(ReadSaveError::MissingField("white"))?,
+            clock,
+        })
+    }
+}
+
+// the kind ends at the first space and the name is trimmed, both within a single line
+fn check_player(player: &SavedPlayer) -> io::Result<()> {
+    let invalid = |msg| Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    if player.kind.is_empty()
+        || player
+            .kind
+            .chars()
+            .any(|ch| ch.is_whitespace() || ch.is_control())
+    {
+        return invalid(format!(
+            "invalid player kind `{}`",
+            player.kind.escape_debug()
+        ));
+    }
+    if player.name.trim() != player.name || player.name.chars().any(char::is_control) {
+        return invalid(format!(
+            "invalid player name `{}`",
+            player.name.escape_debug()
+        ));
+    }
+    Ok(())
+}
+
+fn parse_moves(line: usize, s: &str) -> Result<Vec<Move>, ReadSaveError> {
+    s.split_ascii_whitespace()
+        .map(|mv| {
+            if mv.eq_ignore_ascii_case("pass") {
+                return Ok(Move::Pass);
+            }
+            mv.parse::<Pos>()
+                .map(Move::Put)
+                .map_err(|source| ReadSaveError::Move {
+                    line,
+                    mv: mv.into(),
+                    source,
+                })
+        })
+        .collect()
+}
+
+fn parse_player(line: usize, s: &str) -> Result<SavedPlayer, ReadSaveError> {
+    let (kind, name) = s.split_once(' ').unwrap_or((s, ""));
+    if kind.is_empty() {
+        return Err(ReadSaveError::Player { line });
+    }
+    Ok(SavedPlayer {
+        kind: kind.into(),
+        name: name.trim().into(),
+    })
+}
+
+fn parse_clock(line: usize, s: &str) -> Result<SavedClock, ReadSaveError> {
+    let mut fields = s.split_ascii_whitespace().map(|value| {
+        value
+            .parse::<u64>()
+            .map(Duration::from_millis)
+            .map_err(|source| ReadSaveError::ClockTime {
+                line,
+                value: value.into(),
+                source,
+            })
+    });
+    let (black, white) = match (fields.next(), fields.next(), fields.next()) {
+        (Some(black), Some(white), None) => (black?, white?),
+        _ => return Err(ReadSaveError::Clock { line }),
+    };
+    Ok(SavedClock { black, white })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::pass_game, PutError};
+
+    const SAVE: &str = "\
+reversi-save 1
+position ---------------------------OX------XO--------------------------- X
+moves d3 c3 f5 d2 d1 e1 b2 c1 pass
+black human Alice Smith
+white computer-3 Computer
+clock 295000 281500
+";
+
+    #[test]
+    fn round_trip() {
+        let data = SaveData::read(SAVE.as_bytes()).unwrap();
+        assert_eq!(data.game.moves(), pass_game().moves());
+        assert_eq!(data.game.turn_color(), Some(Color::White));
+        assert_eq!(data.player(Color::Black).name, "Alice Smith");
+        assert_eq!(data.player(Color::White).kind, "computer-3");
+        assert_eq!(
+            data.clock,
+            Some(SavedClock {
+                black: Duration::from_millis(295000),
+                white: Duration::from_millis(281500),
+            })
+        );
+
+        let mut buf = vec![];
+        data.write(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), SAVE);
+
+        let mut data = SaveData::new(
+            Game::new(),
+            SavedPlayer {
+                kind: "human".into(),
+                name: "".into(),
+            },
+            data.white,
+        );
+        data.game.put_disk(Pos::F5).unwrap();
+        let mut buf = vec![];
+        data.write(&mut buf).unwrap();
+        let read = SaveData::read(buf.as_slice()).unwrap();
+        assert_eq!(read.game.moves(), data.game.moves());
+        assert_eq!(read.black, data.black);
+        assert_eq!(read.clock, None);
+
+        data.black.name = "Bob  the  Builder".into();
+        let mut buf = vec![];
+        data.write(&mut buf).unwrap();
+        assert_eq!(SaveData::read(buf.as_slice()).unwrap().black, data.black);
+    }
+
+    #[test]
+    fn reject_unwritable_player() {
+        let player = |kind: &str, name: &str| SavedPlayer {
+            kind: kind.into(),
+            name: name.into(),
+        };
+        for (kind, name) in [
+            ("", "Alice"),
+            ("computer 3", "Computer"),
+            ("computer\t3", "Computer"),
+            ("human", " Alice"),
+            ("human", "Alice "),
+            ("human", "Alice\nclock 0 0"),
+            ("human", "Alice\r"),
+        ] {
+            let data = SaveData::new(Game::new(), player(kind, name), player("human", "Bob"));
+            let mut buf = vec![];
+            let err = data.write(&mut buf).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            assert!(buf.is_empty());
+        }
+    }
+
+    #[test]
+    fn reject() {
+        assert!(matches!(
+            SaveData::read("".as_bytes()),
+            Err(ReadSaveError::MissingHeader)
+        ));
+        assert!(matches!(
+            SaveData::read(SAVE.replace("save 1", "save 2").as_bytes()),
+            Err(ReadSaveError::UnsupportedVersion(v)) if v == "2"
+        ));
+        assert!(matches!(
+            SaveData::read(SAVE.replace("f5 d2", "f5 a1").as_bytes()),
+            Err(ReadSaveError::Replay(ReplayError::IllegalMove {
+                index: 3,
+                source: PutError::CannotPut(Pos::A1)
+            }))
+        ));
+        assert!(matches!(
+            SaveData::read(SAVE.replace(" pass", "").as_bytes()),
+            Err(ReadSaveError::Replay(ReplayError::MissingPass { index: 8 }))
+        ));
+        assert!(matches!(
+            SaveData::read(SAVE.replace("c3 f5", "c3 pass f5").as_bytes()),
+            Err(ReadSaveError::Replay(ReplayError::UnexpectedPass {
+                index: 2
+            }))
+        ));
+        assert!(matches!(
+            SaveData::read(SAVE.replace("b2", "z9").as_bytes()),
+            Err(ReadSaveError::Move { line: 3, .. })
+        ));
+        assert!(matches!(
+            SaveData::read(SAVE.replace("black human Alice Smith\n", "").as_bytes()),
+            Err(ReadSaveError::MissingField("black"))
+        ));
+        assert!(matches!(
+            SaveData::read(format!("{}clock 0 0\n", SAVE).as_bytes()),
+            Err(ReadSaveError::DuplicatedField { line: 7, .. })
+        ));
+        assert!(matches!(
+            SaveData::read(SAVE.replace("281500", "281500 0").as_bytes()),
+            Err(ReadSaveError::Clock { line: 6 })
+        ));
+        assert!(matches!(
+            SaveData::read(format!("{}foo bar\n", SAVE).as_bytes()),
+            Err(ReadSaveError::UnknownField { line: 7, .. })
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{Board, Color, Game, Move, Pos, PosSet, Position};
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+impl Serialize for Pos {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&self.to_string().to_ascii_lowercase())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pos {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Pos::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for PosSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
+impl<'de> Deserialize<'de> for PosSet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(PosSet::from_bits)
+    }
+}
+
+impl Serialize for Board {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            let (mine, others) = self.disk_sets();
+            [mine, others].serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            Board::from_str(&s).map_err(de::Error::custom)
+        } else {
+            let [mine, others] = <[PosSet; 2]>::deserialize(deserializer)?;
+            Board::from_disk_sets(mine, others)
+                .ok_or_else(|| de::Error::custom("a square is occupied by both sides"))
+        }
+    }
+}
+
+impl Serialize for Position {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.board(), self.to_move()).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Position {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            Position::from_str(&s).map_err(de::Error::custom)
+        } else {
+            let (board, to_move) = <(Board, Color)>::deserialize(deserializer)?;
+            Ok(Position::new(board, to_move))
+        }
+    }
+}
+
+impl Serialize for Move {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Move::Put(pos) => pos.serialize(serializer),
+            Move::Pass => serializer.serialize_str("pass"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Move {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        if s.eq_ignore_ascii_case("pass") {
+            return Ok(Move::Pass);
+        }
+        Pos::from_str(&s).map(Move::Put).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Game {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let moves = self
+            .moves()
+            .iter()
+            .map(|record| record.mv)
+            .collect::<Vec<_>>();
+        let mut state = serializer.serialize_struct("Game", 2)?;
+        state.serialize_field("start", self.start_position())?;
+        state.serialize_field("moves", &moves)?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Game")]
+struct GameRepr {
+    start: Position,
+    moves: Vec<Move>,
+}
+
+impl<'de> Deserialize<'de> for Game {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let GameRepr { start, moves } = GameRepr::deserialize(deserializer)?;
+        Game::replay(start, &moves).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::pass_game;
+
+    #[test]
+    fn json() {
+        assert_eq!(serde_json::to_string(&Pos::E4).unwrap(), r#""e4""#);
+        assert_eq!(serde_json::from_str::<Pos>(r#""E4""#).unwrap(), Pos::E4);
+        assert!(serde_json::from_str::<Pos>(r#""i9""#).is_err());
+
+        let set = PosSet::new() | Pos::A1 | Pos::H8;
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(json, (1u64 | 1 << 63).to_string());
+        assert_eq!(serde_json::from_str::<PosSet>(&json).unwrap(), set);
+
+        let board = Board::new();
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(json, format!(r#""{}""#, board));
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+
+        assert_eq!(serde_json::to_string(&Color::White).unwrap(), r#""White""#);
+        assert_eq!(serde_json::to_string(&Move::Pass).unwrap(), r#""pass""#);
+    }
+
+    #[test]
+    fn game() {
+        // black passes after white's c1
+        let game = pass_game();
+        let json = serde_json::to_string(&game).unwrap();
+        assert!(json.ends_with(r#""moves":["d3","c3","f5","d2","d1","e1","b2","c1","pass"]}"#));
+
+        let de = serde_json::from_str::<Game>(&json).unwrap();
+        assert_eq!(de.moves(), game.moves());
+        assert_eq!(de.position(), game.position());
+
+        assert!(serde_json::from_str::<Game>(&json.replace(r#","pass""#, "")).is_err());
+
+        let bytes = bincode::serialize(&game).unwrap();
+        let de = bincode::deserialize::<Game>(&bytes).unwrap();
+        assert_eq!(de.moves(), game.moves());
+        assert_eq!(de.position(), game.position());
+        assert!(serde_json::from_str::<Game>(&json.replace("c1", "a1")).is_err());
+    }
+}
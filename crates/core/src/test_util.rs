@@ -0,0 +1,41 @@
+// shared fixtures of the unit tests
+use crate::{Board, Game, Pos};
+use rand::prelude::*;
+
+// white wins 45-19, with four passes on the way
+pub(crate) const FULL_GAME: &str =
+    "e6f6g6g7g8h8f5f8f7e7e8d8h7h6c4d7c8b8c7d6g4g5h4h5f4h3c5c6b6b7a7a8\
+                                    a6a5b5f3b4a4g3f2e3d3c3b3a3a2h2g2h1e2d2c2b2b1g1f1e1d1c1a1";
+
+// white's last move leaves black without a move
+pub(crate) const PASS_MOVES: [Pos; 8] = [
+    Pos::D3,
+    Pos::C3,
+    Pos::F5,
+    Pos::D2,
+    Pos::D1,
+    Pos::E1,
+    Pos::B2,
+    Pos::C1,
+];
+
+pub(crate) fn pass_game() -> Game {
+    let mut game = Game::new();
+    for pos in PASS_MOVES {
+        game.put_disk(pos).unwrap();
+    }
+    game
+}
+
+// plays random moves from the initial position, stopping early at the end of the game
+pub(crate) fn random_board(rng: &mut impl Rng, plies: u32) -> Board {
+    let mut board = Board::new();
+    for _ in 0..plies {
+        match board.all_flipped().choose(rng) {
+            Some((_, flipped)) => board = flipped,
+            None if board.reverse().can_play() => board = board.reverse(),
+            None => break,
+        }
+    }
+    board
+}
@@ -62,7 +62,7 @@ mod test {
     fn iter_ones() {
         (0b10101u8).iter_one_bits().eq([1, 1 << 2, 1 << 4]);
         (0b10101u8).iter_one_bits().rev().eq([1 << 4, 1 << 2, 0]);
-        0u8.iter_one_bits().eq([]);
-        0u8.iter_one_bits().rev().eq([]);
+        0u8.iter_one_bits().eq([0u8; 0]);
+        0u8.iter_one_bits().rev().eq([0u8; 0]);
     }
 }
@@ -44,7 +44,8 @@ impl Game {
 #[derive(Debug)]
 enum GameState {
     Config(ConfigState),
-    Play(PlayState),
+    // boxed as the game with its start position is much larger than the other states
+    Play(Box<PlayState>),
     Closed,
 }
 
@@ -29,7 +29,7 @@ impl ConfigState {
 
         ui.horizontal(|ui| {
             if ui.button("Play").clicked() {
-                new_state = Some(GameState::Play(PlayState::new(self.clone())));
+                new_state = Some(GameState::Play(Box::new(PlayState::new(self.clone()))));
             }
             if ui.button("Cancel").clicked() {
                 new_state = Some(GameState::Closed);
@@ -6,9 +6,8 @@ use eframe::{
 };
 use rand::prelude::*;
 use reversi_com::{Com, NextMove, WeightEvaluator};
-use reversi_core::{Color, Game, Pos};
+use reversi_core::{Color, Game, Move, OpeningCatalog, Pos};
 use std::{
-    cmp::Ordering,
     fs::File,
     io::BufReader,
     path::Path,
@@ -24,7 +23,9 @@ pub(super) struct PlayState {
     computer1: Option<Computer>,
     computer2: Option<Computer>,
     game: Game,
+    openings: OpeningCatalog,
     last_put: Option<Pos>,
+    expected_line: Vec<Move>,
     messages: Vec<String>,
     state: GameState,
 }
@@ -88,7 +89,9 @@ impl PlayState {
             computer1,
             computer2,
             game: Game::new(),
+            openings: OpeningCatalog::new(),
             last_put: None,
+            expected_line: vec![],
             messages: vec![],
             state: GameState::Init,
         }
@@ -108,6 +111,8 @@ impl PlayState {
         ui.vertical_centered(|ui| {
             ui_score_board(ui, &self.game);
             ui_game_status_label(ui, &self.game, &self.config);
+            ui_opening_label(ui, &self.game, &self.openings);
+            ui_expected_line_label(ui, &self.expected_line);
 
             let is_human_turn = matches!(self.state, GameState::WaitHuman);
             if let Some(pos) = board::show(ui, &self.game, is_human_turn, self.last_put) {
@@ -123,7 +128,10 @@ impl PlayState {
             GameState::Init => {}
             GameState::WaitHuman => {}
             GameState::WaitComputer(rx) => match rx.try_recv() {
-                Ok(next_move) => self.put(ui, frame, next_move.chosen.unwrap().0),
+                Ok(next_move) => {
+                    self.expected_line = next_move.pv;
+                    self.put(ui, frame, next_move.chosen.unwrap().0)
+                }
                 Err(mpsc::TryRecvError::Empty) => {}
                 Err(mpsc::TryRecvError::Disconnected) => panic!(),
             },
@@ -132,9 +140,18 @@ impl PlayState {
     }
 
     fn put(&mut self, ui: &mut egui::Ui, frame: &mut epi::Frame, pos: Pos) {
+        let played = self.game.moves().len();
         match self.game.put_disk(pos) {
-            Ok(()) => {
+            Ok(_) => {
                 self.last_put = Some(pos);
+                // the expected line is kept only as long as the game follows it
+                for record in &self.game.moves()[played..] {
+                    if self.expected_line.first() == Some(&record.mv) {
+                        self.expected_line.remove(0);
+                    } else {
+                        self.expected_line.clear();
+                    }
+                }
                 self.update_state(ui, frame);
             }
             Err(e) => {
@@ -224,6 +241,24 @@ fn ui_score_board(ui: &mut egui::Ui, game: &Game) {
     draw(Color::White, 3.0 / 4.0);
 }
 
+fn ui_opening_label(ui: &mut egui::Ui, game: &Game, openings: &OpeningCatalog) {
+    if let Some(opening) = openings.identify(game) {
+        ui.label(format!("Opening: {}", opening.name()));
+    }
+}
+
+fn ui_expected_line_label(ui: &mut egui::Ui, expected_line: &[Move]) {
+    if expected_line.is_empty() {
+        return;
+    }
+    let line = expected_line
+        .iter()
+        .map(|mv| mv.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    ui.label(format!("Expected line: {}", line));
+}
+
 fn ui_game_status_label(ui: &mut egui::Ui, game: &Game, config: &ConfigState) {
     if let Some(color) = game.turn_color() {
         let player = config.player(color);
@@ -231,19 +266,17 @@ fn ui_game_status_label(ui: &mut egui::Ui, game: &Game, config: &ConfigState) {
         return;
     }
 
-    let winner = match game
-        .count_disk(Some(Color::Black))
-        .cmp(&game.count_disk(Some(Color::White)))
-    {
-        Ordering::Less => Some(Color::White),
-        Ordering::Equal => None,
-        Ordering::Greater => Some(Color::Black),
+    let result = match game.result() {
+        Some(result) => result,
+        None => return,
     };
+    let black = result.score(Color::Black);
+    let white = result.score(Color::White);
 
-    if let Some(winner) = winner {
+    if let Some(winner) = result.winner() {
         let player = config.player(winner);
-        ui.heading(format!("{} win", player.name));
+        ui.heading(format!("{} win ({}-{})", player.name, black, white));
     } else {
-        ui.heading("draw");
+        ui.heading(format!("draw ({}-{})", black, white));
     }
 }
@@ -2,7 +2,7 @@ use argh::FromArgs;
 use rand::{seq::IteratorRandom, Rng};
 use rayon::prelude::*;
 use reversi_com::{Com, Evaluate as _, WeightEvaluator, WeightUpdater};
-use reversi_core::{Board, Color};
+use reversi_core::{Board, Position};
 use std::{
     fmt,
     fs::File,
@@ -119,7 +119,6 @@ fn div_ceil(n: u32, m: u32) -> u32 {
 fn main() -> Result<(), Error> {
     let args: Args = argh::from_env();
     let evaluator = read_evaluator(&args)?;
-    let com = Com::new(4, 12, 12);
     let mut updater = WeightUpdater::new(evaluator);
 
     const FLUSH_INTERVAL: u32 = 10;
@@ -133,7 +132,8 @@ fn main() -> Result<(), Error> {
             let evaluator = updater.evaluator().clone();
             (0..FLUSH_INTERVAL)
                 .into_par_iter()
-                .map(|_| play_game(&evaluator, &com))
+                // each worker keeps its own computer, as it owns the transposition table
+                .map_init(|| Com::new(4, 12, 12), |com, _| play_game(&evaluator, com))
                 .collect::<Vec<_>>()
                 .into_iter()
                 .for_each(|(history, elapsed, visited_nodes)| {
@@ -186,80 +186,74 @@ fn write_evaluator(args: &Args, evaluator: &WeightEvaluator) -> Result<(), Error
     Ok(())
 }
 
-fn play_game(evaluator: &WeightEvaluator, com: &Com) -> (Vec<(Board, Color)>, Duration, u32) {
+fn play_game(evaluator: &WeightEvaluator, com: &Com) -> (Vec<Position>, Duration, u32) {
     let mut rng = rand::thread_rng();
-    let mut board = Board::new();
-    let mut color = Color::Black;
+    let mut position = Position::default();
     let mut total_duration = Duration::ZERO;
     let mut total_visited_nodes = 0;
 
     let mut history = Vec::with_capacity(64);
 
     for _ in 0..8 {
-        match board.all_flipped().choose(&mut rng) {
-            Some((_, flipped)) => {
-                history.push((board, color));
-                board = flipped;
-                color = color.reverse();
-            }
-            None => {
-                board = board.reverse();
-                color = color.reverse();
+        match position.board().all_flipped().choose(&mut rng) {
+            Some((pos, _)) => {
+                history.push(position);
+                position = position.play(pos).unwrap();
             }
+            None => position = position.pass(),
         }
     }
 
     loop {
+        let board = position.board();
         let chosen = if board.count_disk(None) > 12 && rng.gen_ratio(1, 100) {
             board.all_flipped().choose(&mut rng)
         } else {
             let start = Instant::now();
-            let next_move = com.next_move(evaluator, &board);
+            let next_move = com.next_move(evaluator, board);
             let elapsed = start.elapsed();
             total_duration += elapsed;
             total_visited_nodes += next_move.visited_nodes;
             next_move.chosen
         };
         match chosen {
-            Some((_pos, flipped)) => {
-                history.push((board, color));
-                board = flipped;
-                color = color.reverse();
+            Some((pos, _flipped)) => {
+                history.push(position);
+                position = position.play(pos).unwrap();
             }
             None => {
-                board = board.reverse();
-                color = color.reverse();
-                if !board.can_play() {
-                    board = board.reverse();
-                    color = color.reverse();
+                if position.is_game_over() {
                     break;
                 }
+                position = position.pass();
             }
         }
     }
-    history.push((board, color));
-    assert!(!board.can_play() && (!board.reverse().can_play()));
+    history.push(position);
+    assert!(position.is_game_over());
     (history, total_duration, total_visited_nodes)
 }
 
-fn update(updater: &mut WeightUpdater, history: &[(Board, Color)]) -> i32 {
+fn update(updater: &mut WeightUpdater, history: &[Position]) -> i32 {
     let mut history = history.iter().rev().copied();
 
-    let (mut board, last_color) = history.next().unwrap();
-    let result = updater.evaluator().evaluate(&board, true);
+    let last = history.next().unwrap();
+    let last_color = last.to_move();
+    let result = updater.evaluator().evaluate(last.board(), true);
 
-    while board.count_disk(None) < 8 {
-        board = history.next().unwrap().0;
+    let mut position = last;
+    while position.board().count_disk(None) < 8 {
+        position = history.next().unwrap();
     }
 
     let mut total_dist = 0;
     let mut count = 0;
-    for _ in (board.count_disk(None) as i8)..(Board::SIZE * Board::SIZE - 12) {
-        let (board, color) = history.next().unwrap();
-        let diff = if color == last_color {
-            updater.update(&board, result)
+    for _ in (position.board().count_disk(None) as i8)..(Board::SIZE * Board::SIZE - 12) {
+        let position = history.next().unwrap();
+        let diff = if position.to_move() == last_color {
+            updater.update(position.board(), result)
         } else {
-            updater.update(&board, -result)
+            updater.update(position.board(), -result)
         };
         total_dist += diff.abs();
         count += 1;
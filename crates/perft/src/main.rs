@@ -0,0 +1,33 @@
+use argh::FromArgs;
+use reversi_core::Position;
+use std::time::Instant;
+
+/// Count leaf nodes of the game tree to verify move generation
+#[derive(Debug, FromArgs)]
+struct Args {
+    /// start position in OBF-style notation (default: initial position)
+    #[argh(option)]
+    position: Option<Position>,
+    /// maximum depth
+    #[argh(positional, default = "10")]
+    depth: u32,
+}
+
+fn main() {
+    let args: Args = argh::from_env();
+    let position = args.position.unwrap_or_default();
+
+    println!("{}", position);
+    for depth in 1..=args.depth {
+        let start = Instant::now();
+        let count = reversi_core::perft(position.board(), depth);
+        let elapsed = start.elapsed();
+        println!(
+            "depth {:2}: {:14} ({:.3} sec, {:.2} MNPS)",
+            depth,
+            count,
+            elapsed.as_secs_f64(),
+            count as f64 / elapsed.as_secs_f64() / 1_000_000.0
+        );
+    }
+}